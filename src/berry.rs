@@ -0,0 +1,247 @@
+//! Momentum-space Berry curvature of the bosonic Bogoliubov bands, and the magnon thermal
+//! Hall conductivity built from it.
+use std::f64::consts::PI;
+
+use faer::{Col, Mat, MatRef};
+use rayon::prelude::*;
+
+use crate::constants::K_B;
+use crate::spinwave::{bogoliubov_modes, calc_AB_gradient, calc_q_independent, calc_sqrt_hamiltonian};
+use crate::utils::block_matrix;
+use crate::{Coupling, MagneticField, C64};
+
+/// The bosonic commutation metric `Sigma = diag(I_n, -I_n)` as a column of its diagonal
+/// entries (matching the `2n x 2n` layout of the paraunitary transform `T`).
+pub(crate) fn sigma_diag(n_sites: usize) -> Col<C64> {
+    Col::<C64>::from_fn(2 * n_sites, |i| {
+        if i < n_sites {
+            C64::from(1.)
+        } else {
+            C64::from(-1.)
+        }
+    })
+}
+
+/// Assemble `dH/dq[direction]` the same way [`crate::spinwave::calc_sqrt_hamiltonian`]
+/// assembles `H` itself, from the analytic gradient of its `A`/`B` blocks. The q-independent
+/// `C`, `Az` and anisotropy diagonal terms drop out, so `dH = block_matrix(dA, dB, dB^, dA^)`.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn dynamical_matrix_gradient(
+    q: &Col<f64>,
+    direction: usize,
+    n_sites: usize,
+    z: &[Col<C64>],
+    spin_coefficients: &Mat<C64>,
+    couplings: &[&Coupling],
+    biquadratic_factors: &[C64],
+) -> Mat<C64> {
+    let (dA, dB) = calc_AB_gradient(
+        q,
+        direction,
+        n_sites,
+        z,
+        spin_coefficients,
+        couplings,
+        biquadratic_factors,
+    );
+    let dB_adj = dB.adjoint().to_owned();
+    let dA_adj = dA.adjoint().to_owned();
+    block_matrix(&dA, &dB, &dB_adj, &dA_adj)
+}
+
+/// Calculate the Berry curvature `Omega_n^{xy}(q)` of every Bogoliubov band at a single
+/// q-point, via
+/// `Omega_n = i * Sigma_nn * sum_{m != n} [M_x(n,m) M_y(m,n) - M_y(n,m) M_x(m,n)] / (E_n - E_m)^2`
+/// where `M_{x,y} = T^dagger Sigma (dH/dq_{x,y}) T` and `E_n` is the (signed) energy of mode
+/// `n` (negative for the hole branch), following Matsumoto & Murakami (2011).
+#[allow(clippy::too_many_arguments)]
+fn berry_curvature_single_q(
+    q: Col<f64>,
+    C: &Mat<C64>,
+    n_sites: usize,
+    z: &[Col<C64>],
+    spin_coefficients: &Mat<C64>,
+    couplings: &[&Coupling],
+    Az: &Option<Vec<C64>>,
+    biquadratic_factors: &[C64],
+    anisotropy_ab: &Option<Vec<(C64, C64)>>,
+) -> (Vec<f64>, Vec<f64>) {
+    let dim = 2 * n_sites;
+
+    let solution = calc_sqrt_hamiltonian(
+        q.clone(),
+        C,
+        n_sites,
+        z,
+        spin_coefficients,
+        couplings,
+        Az,
+        biquadratic_factors,
+        anisotropy_ab,
+        None,
+    );
+    let (eigvals, T, _, _) = bogoliubov_modes(solution, n_sites);
+    // `bogoliubov_modes` returns `eigvals` in ascending order but `T`'s columns in the
+    // opposite, nonincreasing order; reverse so `eigvals[n]` is the energy of column `n`
+    let eigvals: Vec<f64> = eigvals.into_iter().rev().collect();
+
+    let dHx = dynamical_matrix_gradient(&q, 0, n_sites, z, spin_coefficients, couplings, biquadratic_factors);
+    let dHy = dynamical_matrix_gradient(&q, 1, n_sites, z, spin_coefficients, couplings, biquadratic_factors);
+
+    let sigma = sigma_diag(n_sites);
+    let Mx = T.adjoint() * sigma.as_diagonal() * dHx * T.as_ref();
+    let My = T.adjoint() * sigma.as_diagonal() * dHy * T.as_ref();
+
+    let curvatures: Vec<f64> = (0..dim)
+        .map(|n| {
+            let sigma_n = sigma[n].re;
+            let mut omega = 0.;
+            for m in 0..dim {
+                if m == n {
+                    continue;
+                }
+                let denom = (eigvals[n] - eigvals[m]).powi(2);
+                if denom < 1e-12 {
+                    continue;
+                }
+                let cross = Mx[(n, m)] * My[(m, n)] - My[(n, m)] * Mx[(m, n)];
+                omega += sigma_n * (C64::new(0., 1.) * cross).re / denom;
+            }
+            omega
+        })
+        .collect();
+
+    (eigvals, curvatures)
+}
+
+/// Calculate the Berry curvature of every Bogoliubov band at every q-vector.
+///
+/// Only the [`crate::SpinMode::Dipole`] mode is supported; the generalized SU(N) Berry
+/// curvature is not yet implemented.
+///
+/// # Returns
+/// A vector over q, where each element is a vector over the `2 * n_sites` Bogoliubov bands
+/// (in the usual nonincreasing `+/-` mirrored order) giving `Omega_n^{xy}(q)`.
+pub fn berry_curvature(
+    rotations: Vec<MatRef<C64>>,
+    magnitudes: Vec<f64>,
+    q_vectors: Vec<Vec<f64>>,
+    couplings: Vec<&Coupling>,
+    field: Option<MagneticField>,
+    anisotropy: Option<Vec<Mat<C64>>>,
+) -> Vec<Vec<f64>> {
+    let n_sites = rotations.len();
+    let (C, z, spin_coefficients, Az, biquadratic_factors, anisotropy_ab) =
+        calc_q_independent(rotations, magnitudes, &couplings, field, &anisotropy);
+
+    q_vectors
+        .into_par_iter()
+        .map(|q| {
+            berry_curvature_single_q(
+                Col::from_iter(q),
+                &C,
+                n_sites,
+                &z,
+                &spin_coefficients,
+                &couplings,
+                &Az,
+                &biquadratic_factors,
+                &anisotropy_ab,
+            )
+            .1
+        })
+        .collect()
+}
+
+/// Dilogarithm `Li2(z)` for real `z <= 0`: the direct series for `|z| <= 1`, and the
+/// standard inversion identity `Li2(z) = -Li2(1/z) - pi^2/6 - ln(-z)^2/2` for `z < -1`
+/// (no special-function crate is used elsewhere in this codebase, so this is hand-rolled
+/// to the same standard as the LAPACK wrappers in `eigs.rs`).
+fn dilog(z: f64) -> f64 {
+    if z == 0. {
+        return 0.;
+    }
+    if z >= -1. {
+        let mut term = z;
+        let mut sum = 0.;
+        for k in 1..200 {
+            sum += term / (k * k) as f64;
+            term *= z;
+            if term.abs() < 1e-16 {
+                break;
+            }
+        }
+        sum
+    } else {
+        -dilog(1. / z) - PI * PI / 6. - 0.5 * (-z).ln().powi(2)
+    }
+}
+
+/// Bose-weighted kernel `c2(rho)` for the magnon thermal Hall conductivity (Matsumoto &
+/// Murakami, 2011), built from the dilogarithm. `rho` is the Bose occupation number.
+fn c2(rho: f64) -> f64 {
+    if rho <= 0. {
+        return 0.;
+    }
+    (1. + rho) * ((1. + rho) / rho).ln().powi(2) - rho.ln().powi(2) - 2. * dilog(-rho)
+}
+
+/// Calculate the magnon thermal Hall conductivity
+/// `kappa_xy = -(k_B^2 * T / cell_volume) * sum_n (1 / n_q) * sum_q c2(rho_n(q)) * Omega_n(q)`,
+/// approximating the Brillouin zone integral by its average over the sampled q-grid (the
+/// same grid-average convention used by [`crate::dos::magnon_dos`]).
+///
+/// # Parameters
+/// - `rotations`, `magnitudes`, `couplings`, `field`, `anisotropy`: same as in
+///   [`crate::spinwave::calc_energies`] (Dipole mode only).
+/// - `q_vectors`: the q-points to sample, e.g. a uniform grid over the Brillouin zone.
+/// - `temperature`: the sample temperature in Kelvin.
+/// - `cell_volume`: the real-space unit cell volume, in the same length units as the
+///   reciprocal lattice vectors implicit in `q_vectors`.
+#[allow(clippy::too_many_arguments)]
+pub fn thermal_hall(
+    rotations: Vec<MatRef<C64>>,
+    magnitudes: Vec<f64>,
+    q_vectors: Vec<Vec<f64>>,
+    couplings: Vec<&Coupling>,
+    field: Option<MagneticField>,
+    anisotropy: Option<Vec<Mat<C64>>>,
+    temperature: f64,
+    cell_volume: f64,
+) -> f64 {
+    let n_sites = rotations.len();
+    let n_q = q_vectors.len() as f64;
+    let (C, z, spin_coefficients, Az, biquadratic_factors, anisotropy_ab) =
+        calc_q_independent(rotations, magnitudes, &couplings, field, &anisotropy);
+
+    let sum: f64 = q_vectors
+        .into_par_iter()
+        .map(|q| {
+            let (energies, curvatures) = berry_curvature_single_q(
+                Col::from_iter(q),
+                &C,
+                n_sites,
+                &z,
+                &spin_coefficients,
+                &couplings,
+                &Az,
+                &biquadratic_factors,
+                &anisotropy_ab,
+            );
+            energies
+                .iter()
+                .zip(curvatures)
+                .map(|(&e, omega)| {
+                    let rho = if temperature <= 0. || e <= 0. {
+                        0.
+                    } else {
+                        1. / ((e / (K_B * temperature)).exp() - 1.)
+                    };
+                    c2(rho) * omega
+                })
+                .sum::<f64>()
+        })
+        .sum();
+
+    -(K_B * K_B * temperature) / cell_volume * sum / n_q
+}
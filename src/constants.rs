@@ -10,3 +10,6 @@ pub static SCALAR_J: Scale<C64> = Scale(J);
 
 // Bohr magneton in units meV/T
 pub static MU_B: f64 = 0.05788382;
+
+// Boltzmann constant in units meV/K
+pub static K_B: f64 = 0.08617333;
@@ -4,7 +4,7 @@ use std::cmp::max;
 
 type C64 = Complex<f64>;
 
-use lapack::zheev;
+use lapack::{zgeev, zheev};
 extern crate lapack_src;
 
 /// Calculate eigenvalues and optionally eigenvectors for a square complex Hermitian matrix.
@@ -85,3 +85,88 @@ pub fn eigs(
         _ => unreachable!(),
     }
 }
+
+/// Calculate eigenvalues and right eigenvectors for a general (non-Hermitian) square
+/// complex matrix. Wrapper around the LAPACK `zgeev` routine.
+/// See `zgeev` docs here: https://netlib.org/lapack/explore-html/d9/dd1/group__geev_ga4436c5e7b1d5fb35b28e1ff0921f6544.html
+///
+/// Used as the fallback diagonalization for the bosonic Bogoliubov problem `g * H` when
+/// `H` is not positive definite (e.g. at Goldstone/zero modes), since that matrix is in
+/// general not Hermitian.
+pub fn eigs_general(
+    mut matrix: DMatrix<C64>,
+) -> Result<(DVector<C64>, DMatrix<C64>), &'static str> {
+    let n = matrix.shape().0 as i32;
+    let m: &mut [C64] = matrix.as_mut_slice();
+
+    // eigenvalues
+    let mut eigenvalues = DVector::<C64>::zeros(n as usize);
+    let w: &mut [C64] = eigenvalues.as_mut_slice();
+
+    // left eigenvectors are not needed
+    let mut vl = [Complex::from(0.)];
+
+    // right eigenvectors
+    let mut eigenvectors = DMatrix::<C64>::zeros(n as usize, n as usize);
+    let vr: &mut [C64] = eigenvectors.as_mut_slice();
+
+    let mut info = 0;
+    let mut rwork = vec![0.; max(1, 2 * (n as usize))];
+
+    // if `lwork = -1`, `zgeev` just calculates the optimal workspace size
+    let mut placeholder = [Complex::from(0.)];
+    unsafe {
+        zgeev(
+            b'N', // jobvl: don't compute left eigenvectors
+            b'V', // jobvr: compute right eigenvectors
+            n,
+            m,
+            n,
+            w,
+            &mut vl,
+            1,
+            vr,
+            n,
+            &mut placeholder,
+            -1,
+            &mut rwork,
+            &mut info,
+        )
+    }
+
+    match info {
+        0 => (),
+        x if x < 0 => return Err("LAPACK error: illegal argument."),
+        x if x > 0 => return Err("LAPACK error: eigenvalue algorithm failed to converge"),
+        _ => (),
+    };
+
+    let lwork = placeholder[0].re as i32;
+
+    let mut workspace = vec![Complex::from(0.); lwork as usize];
+    unsafe {
+        zgeev(
+            b'N',
+            b'V',
+            n,
+            m,
+            n,
+            w,
+            &mut vl,
+            1,
+            vr,
+            n,
+            &mut workspace,
+            lwork,
+            &mut rwork,
+            &mut info,
+        )
+    }
+
+    match info {
+        x if x < 0 => Err("LAPACK error: illegal argument."),
+        x if x > 0 => Err("LAPACK error: eigenvalue algorithm failed to converge"),
+        0 => Ok((eigenvalues, eigenvectors)),
+        _ => unreachable!(),
+    }
+}
@@ -7,14 +7,22 @@ use faer::{Col, ColRef, Mat, MatRef};
 use faer_ext::{IntoFaer, IntoNdarray};
 use num_complex::Complex;
 use numpy::{PyArray1, PyArray2, PyReadonlyArray1, PyReadonlyArray2, ToPyArray};
+use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 
 mod spinwave;
-use crate::spinwave::{calc_energies, calc_spinwave};
+use crate::spinwave::{calc_energies, calc_spinwave, ordered_moment as calc_ordered_moment};
 
+mod berry;
 mod constants;
+mod dos;
+mod eigs;
+mod postprocessing;
 mod utils;
+mod velocities;
+
+use crate::dos::DosMethod;
 
 // convenience type for complex arithmetic
 type C64 = Complex<f64>;
@@ -23,6 +31,9 @@ type C64 = Complex<f64>;
 type Energies<'py> = Vec<Bound<'py, PyArray1<f64>>>;
 type SabTensor<'py> = Vec<Vec<Bound<'py, PyArray2<C64>>>>;
 type SQw<'py> = Vec<Bound<'py, PyArray1<f64>>>;
+// indexed (q, atom): each entry is a (mode x 9) matrix of the atom's projected Sab
+// contribution, with column `alpha * 3 + beta` holding the flattened alpha,beta tensor
+type SublatticeSab<'py> = Vec<Vec<Bound<'py, PyArray2<C64>>>>;
 
 /// Temporary description of the coupling between atoms.
 #[pyclass(frozen)]
@@ -31,22 +42,27 @@ pub struct Coupling {
     index2: usize,
     matrix: Mat<C64>,
     inter_site_vector: Col<f64>,
+    /// Biquadratic exchange coefficient K for the K*(S_i . S_j)^2 term, if present.
+    biquadratic: Option<f64>,
 }
 
 #[pymethods]
 impl Coupling {
     #[new]
+    #[pyo3(signature = (index1, index2, matrix, inter_site_vector, biquadratic=None))]
     fn new(
         index1: usize,
         index2: usize,
         matrix: PyReadonlyArray2<C64>,
         inter_site_vector: PyReadonlyArray1<f64>,
+        biquadratic: Option<f64>,
     ) -> Self {
         Coupling {
             index1,
             index2,
             matrix: matrix.into_faer().to_owned(),
             inter_site_vector: inter_site_vector.into_faer().to_owned(),
+            biquadratic,
         }
     }
 }
@@ -72,6 +88,89 @@ impl MagneticField {
     }
 }
 
+/// Selects between the standard dipolar LSWT expansion (one Holstein-Primakoff boson
+/// per site) and the generalized SU(N) flavor-wave expansion (N - 1 bosons per site,
+/// where N = 2S+1 is the local Hilbert space dimension). The dipole mode is the
+/// special case N = 2.
+#[pyclass(eq, eq_int)]
+#[derive(Clone, Copy, PartialEq)]
+pub enum SpinMode {
+    Dipole,
+    GeneralizedSUN,
+}
+
+/// Per-site local basis data required for the generalized SU(N) flavor-wave expansion.
+///
+/// Holds the matrix elements of `S^alpha` between the local ground state `|0>` and the
+/// `N - 1` excited flavors `|m>`, obtained by diagonalizing the single-site Hamiltonian
+/// in the local (possibly non-dipolar) basis. See `calc_energies_sun` /
+/// `calc_spinwave_sun` for how these enter the quadratic boson Hamiltonian.
+#[pyclass(frozen)]
+pub struct SUNBasis {
+    /// `<0|S^alpha|0>` for alpha = x, y, z.
+    expectation: Col<C64>,
+    /// `<m|S^alpha|0>` as a 3 x (N - 1) matrix, row alpha, column flavor m.
+    linear: Mat<C64>,
+}
+
+#[pymethods]
+impl SUNBasis {
+    #[new]
+    fn new(expectation: PyReadonlyArray1<C64>, linear: PyReadonlyArray2<C64>) -> Self {
+        SUNBasis {
+            expectation: expectation.into_faer().to_owned(),
+            linear: linear.into_faer().to_owned(),
+        }
+    }
+}
+
+impl SUNBasis {
+    /// The 3 x (N - 1) matrix of `<m|S^alpha|0>` matrix elements.
+    pub(crate) fn linear_matrix_elements(&self) -> MatRef<C64> {
+        self.linear.as_ref()
+    }
+
+    /// `<0|S^alpha|0>` for alpha = x, y, z: the classical expectation value of the local
+    /// ground state, generalizing the dipole mode's `eta * S` moment vector.
+    pub(crate) fn expectation(&self) -> ColRef<C64> {
+        self.expectation.as_ref()
+    }
+}
+
+/// Analytic `<j0>` approximation to a magnetic ion's neutron form factor,
+/// `f(s) = A * exp(-a * s^2) + B * exp(-b * s^2) + C * exp(-c * s^2) + D`,
+/// with `s = |q| / 4*pi` in inverse angstroms.
+#[pyclass(frozen)]
+#[derive(Clone, Copy)]
+pub struct FormFactor {
+    A: f64,
+    a: f64,
+    B: f64,
+    b: f64,
+    C: f64,
+    c: f64,
+    D: f64,
+}
+
+#[pymethods]
+impl FormFactor {
+    #[new]
+    #[allow(clippy::too_many_arguments)]
+    fn new(A: f64, a: f64, B: f64, b: f64, C: f64, c: f64, D: f64) -> Self {
+        FormFactor { A, a, B, b, C, c, D }
+    }
+}
+
+impl FormFactor {
+    /// Evaluate `f(s)` at `s = |q| / 4*pi` (inverse angstroms).
+    pub(crate) fn evaluate(&self, s: f64) -> f64 {
+        self.A * (-self.a * s * s).exp()
+            + self.B * (-self.b * s * s).exp()
+            + self.C * (-self.c * s * s).exp()
+            + self.D
+    }
+}
+
 /// Calculate the energies (eigenvalues) for a system.
 ///
 /// # Parameters
@@ -80,10 +179,25 @@ impl MagneticField {
 /// - `q_vectors`: A list of q-vectors where the energies should be calculated.
 /// - `couplings`: A list of `Coupling` objects representing the interactions between atoms
 /// - `field`: An optional `MagneticField` object representing an external magnetic field.
+/// - `anisotropy`: An optional list of 3x3 single-ion anisotropy tensors `D_i`, one per
+///   atom, for the on-site term `S_i^T D_i S_i`.
+/// - `mode`: Whether to use the dipolar LSWT expansion or the generalized SU(N)
+///   flavor-wave expansion. Defaults to `SpinMode::Dipole`.
+/// - `sun_bases`: Per-site `SUNBasis` data; required when `mode` is `GeneralizedSUN`.
+/// - `epsilon`: If given, enables a regularized LDL^T fallback (Tikhonov-style diagonal
+///   shift) for q-points where the Bogoliubov Hamiltonian is not positive definite (e.g. at
+///   Goldstone/zero modes), in preference to the non-Hermitian eigensolver fallback, so
+///   that those q-points get physically meaningful energies instead of being dropped.
 ///
 /// # Returns
-/// A list of 1D numpy arrays, each containing the energies for the corresponding q-vector.
-#[pyfunction(signature = (rotations, magnitudes, q_vectors, couplings, field=None))]
+/// A tuple of:
+/// - A list of 1D numpy arrays, each containing the energies for the corresponding q-vector.
+/// - A list with one entry per q-vector: the largest regularization shift applied if the
+///   `epsilon` fallback was used for that q-point, or `None` otherwise.
+/// - A list with one entry per q-vector: the largest `|Im(eigenvalue)|` if the non-Hermitian
+///   fallback found a dynamically unstable mode at that q-point, or `None` otherwise.
+#[pyfunction(signature = (rotations, magnitudes, q_vectors, couplings, field=None, anisotropy=None, mode=SpinMode::Dipole, sun_bases=None, epsilon=None))]
+#[allow(clippy::too_many_arguments)]
 pub fn energies<'py>(
     py: Python<'py>,
     rotations: Vec<PyReadonlyArray2<C64>>,
@@ -91,7 +205,11 @@ pub fn energies<'py>(
     q_vectors: Vec<Vec<f64>>,
     couplings: Vec<Py<Coupling>>,
     field: Option<MagneticField>,
-) -> PyResult<Energies<'py>> {
+    anisotropy: Option<Vec<PyReadonlyArray2<C64>>>,
+    mode: SpinMode,
+    sun_bases: Option<Vec<Py<SUNBasis>>>,
+    epsilon: Option<f64>,
+) -> PyResult<(Energies<'py>, Vec<Option<f64>>, Vec<Option<f64>>)> {
     // convert PyO3-friendly array types to faer matrices
     let r: Vec<MatRef<C64>> = rotations
         .into_iter()
@@ -99,12 +217,81 @@ pub fn energies<'py>(
         .collect();
 
     let c = couplings.par_iter().map(pyo3::Py::get).collect();
+    let bases = sun_bases.as_ref().map(|b| b.par_iter().map(pyo3::Py::get).collect());
+    let d = anisotropy.map(|ds| ds.into_iter().map(|d| d.into_faer().to_owned()).collect());
 
-    let results = calc_energies(r, magnitudes, q_vectors, c, field);
-    Ok(results
+    let results = calc_energies(r, magnitudes, q_vectors, c, field, d, mode, bases, epsilon);
+    Ok((
+        results
+            .iter()
+            .map(|(e, _, _)| e.to_pyarray(py))
+            .collect(),
+        results.iter().map(|(_, shift, _)| *shift).collect(),
+        results.into_iter().map(|(_, _, unstable)| unstable).collect(),
+    ))
+}
+
+/// Calculate the magnon density of states D(E) on a regular q-grid spanning the first
+/// Brillouin zone, reusing `calc_energies` for the underlying band structure.
+///
+/// # Parameters
+/// - `rotations`, `magnitudes`, `couplings`, `field`, `anisotropy`, `mode`, `sun_bases`: same
+///   as in `energies`.
+/// - `grid_dims`: the number of q-points to sample along each reciprocal lattice direction,
+///   as an `(nx, ny, nz)` tuple.
+/// - `e_min`, `e_max`, `n_bins`: the energy axis, `n_bins` uniformly spaced bins over
+///   `[e_min, e_max]`.
+/// - `method`: either `"gaussian"`, `"lorentzian"`, or `"tetrahedron"`. The linear
+///   tetrahedron method divides each grid microcell into 6 tetrahedra and accumulates the
+///   analytic DOS contribution per band exactly, avoiding the broadening artifacts
+///   Gaussian/Lorentzian smearing introduces near van Hove singularities.
+/// - `broadening`: the Gaussian standard deviation or Lorentzian half-width at
+///   half-maximum. Required (and unused) for `"gaussian"`/`"lorentzian"`
+///   (respectively `"tetrahedron"`).
+///
+/// # Returns
+/// A tuple of a 1D numpy array of DOS values at each energy bin, and the total number of
+/// (q-point, band) modes integrated, for normalization.
+#[pyfunction(signature = (rotations, magnitudes, couplings, grid_dims, e_min, e_max, n_bins, method="gaussian", broadening=None, field=None, anisotropy=None, mode=SpinMode::Dipole, sun_bases=None))]
+#[allow(clippy::too_many_arguments)]
+pub fn magnon_dos<'py>(
+    py: Python<'py>,
+    rotations: Vec<PyReadonlyArray2<C64>>,
+    magnitudes: Vec<f64>,
+    couplings: Vec<Py<Coupling>>,
+    grid_dims: (usize, usize, usize),
+    e_min: f64,
+    e_max: f64,
+    n_bins: usize,
+    method: &str,
+    broadening: Option<f64>,
+    field: Option<MagneticField>,
+    anisotropy: Option<Vec<PyReadonlyArray2<C64>>>,
+    mode: SpinMode,
+    sun_bases: Option<Vec<Py<SUNBasis>>>,
+) -> PyResult<(Bound<'py, PyArray1<f64>>, f64)> {
+    let r: Vec<MatRef<C64>> = rotations
         .into_iter()
-        .map(|result| result.to_pyarray(py))
-        .collect())
+        .map(faer_ext::IntoFaer::into_faer)
+        .collect();
+
+    let c = couplings.par_iter().map(pyo3::Py::get).collect();
+    let bases = sun_bases.as_ref().map(|b| b.par_iter().map(pyo3::Py::get).collect());
+    let d = anisotropy.map(|ds| ds.into_iter().map(|d| d.into_faer().to_owned()).collect());
+
+    let dos_method = match method {
+        "gaussian" => DosMethod::Gaussian(
+            broadening.ok_or_else(|| PyValueError::new_err("`broadening` is required for the 'gaussian' method"))?,
+        ),
+        "lorentzian" => DosMethod::Lorentzian(
+            broadening.ok_or_else(|| PyValueError::new_err("`broadening` is required for the 'lorentzian' method"))?,
+        ),
+        "tetrahedron" => DosMethod::Tetrahedron,
+        other => return Err(PyValueError::new_err(format!("unknown DOS method '{other}'"))),
+    };
+
+    let (dos, n_modes) = dos::magnon_dos(r, magnitudes, c, field, d, mode, bases, grid_dims, e_min, e_max, n_bins, dos_method);
+    Ok((dos.to_pyarray(py), n_modes))
 }
 
 /// Calculate energies and neutron scattering cross-section for a system.
@@ -117,13 +304,24 @@ pub fn energies<'py>(
 /// - `positions`: A list of 1D numpy arrays representing the relative positions of each atom
 ///  in the unit cell.
 /// - `field`: An optional `MagneticField` object representing an external magnetic field.
+/// - `anisotropy`: An optional list of 3x3 single-ion anisotropy tensors `D_i`, one per
+///   atom, for the on-site term `S_i^T D_i S_i`.
+///
+/// - `epsilon`: If given, q-points whose grand dynamical matrix is not positive definite
+///   (e.g. at Goldstone modes) fall back to a regularized LDL factorization instead of
+///   being dropped; see [`calc_spinwave`] for details. If omitted, such q-points fall back
+///   to the non-Hermitian eigensolver instead.
 ///
 /// # Returns
 /// A tuple containing:
 /// - A list of 1D numpy arrays, each containing the energies for the corresponding q-vector.
 /// - A list of 1D numpy arrays, each containing the neutron scattering cross-section
 ///   for the corresponding q-vector (indexed over omega).
-#[pyfunction(signature = (rotations, magnitudes, q_vectors, couplings, positions, field=None))]
+/// - A list of per-q-vector regularization shifts: the largest Tikhonov shift applied to
+///   make the dynamical matrix positive definite if the `epsilon` fallback was used, or
+///   `None` otherwise (Cholesky succeeded, or the non-Hermitian fallback was used).
+#[pyfunction(signature = (rotations, magnitudes, q_vectors, couplings, positions, field=None, anisotropy=None, mode=SpinMode::Dipole, sun_bases=None, form_factors=None, epsilon=None))]
+#[allow(clippy::too_many_arguments)]
 pub fn spinwave_calculation<'py>(
     py: Python<'py>,
     rotations: Vec<PyReadonlyArray2<C64>>,
@@ -132,7 +330,12 @@ pub fn spinwave_calculation<'py>(
     couplings: Vec<Py<Coupling>>,
     positions: Vec<PyReadonlyArray1<f64>>,
     field: Option<MagneticField>,
-) -> PyResult<(Energies<'py>, SQw<'py>)> {
+    anisotropy: Option<Vec<PyReadonlyArray2<C64>>>,
+    mode: SpinMode,
+    sun_bases: Option<Vec<Py<SUNBasis>>>,
+    form_factors: Option<Vec<FormFactor>>,
+    epsilon: Option<f64>,
+) -> PyResult<(Energies<'py>, SQw<'py>, Vec<Option<f64>>)> {
     // convert PyO3-friendly array types to faer matrices
     let r: Vec<MatRef<C64>> = rotations
         .into_iter()
@@ -146,7 +349,10 @@ pub fn spinwave_calculation<'py>(
         .map(faer_ext::IntoFaer::into_faer)
         .collect();
 
-    let results = calc_spinwave(r, magnitudes, q_vectors.clone(), c, p, field, false);
+    let bases = sun_bases.as_ref().map(|b| b.par_iter().map(pyo3::Py::get).collect());
+    let d = anisotropy.map(|ds| ds.into_iter().map(|d| d.into_faer().to_owned()).collect());
+
+    let results = calc_spinwave(r, magnitudes, q_vectors.clone(), c, p, field, d, mode, bases, form_factors, epsilon);
     Ok((
         results
             .iter()
@@ -156,11 +362,22 @@ pub fn spinwave_calculation<'py>(
             .iter()
             .map(|result| result.intensities.to_pyarray(py))
             .collect(),
+        results
+            .iter()
+            .map(|result| result.regularization_shift)
+            .collect(),
     ))
 }
 
 /// Same as spinwave_calculation but also returns Sab tensors.
-#[pyfunction(signature = (rotations, magnitudes, q_vectors, couplings, positions, field=None))]
+///
+/// - `form_factors`: An optional list of per-atom `FormFactor` coefficients for the
+///   analytic `<j0>` neutron form factor. When given, `Sab` is weighted by `f_i(q) * f_j(q)`
+///   for each pair of atoms, since the form factor is folded in at the same stage as the
+///   inter-site phase factors (see `calc_sab_blocks`).
+/// - `epsilon`: see [`spinwave_calculation`].
+#[pyfunction(signature = (rotations, magnitudes, q_vectors, couplings, positions, field=None, anisotropy=None, mode=SpinMode::Dipole, sun_bases=None, form_factors=None, epsilon=None))]
+#[allow(clippy::too_many_arguments)]
 pub fn spinwave_calculation_Sab<'py>(
     py: Python<'py>,
     rotations: Vec<PyReadonlyArray2<C64>>,
@@ -169,7 +386,12 @@ pub fn spinwave_calculation_Sab<'py>(
     couplings: Vec<Py<Coupling>>,
     positions: Vec<PyReadonlyArray1<f64>>,
     field: Option<MagneticField>,
-) -> PyResult<(Energies<'py>, SQw<'py>, SabTensor<'py>)> {
+    anisotropy: Option<Vec<PyReadonlyArray2<C64>>>,
+    mode: SpinMode,
+    sun_bases: Option<Vec<Py<SUNBasis>>>,
+    form_factors: Option<Vec<FormFactor>>,
+    epsilon: Option<f64>,
+) -> PyResult<(Energies<'py>, SQw<'py>, SabTensor<'py>, SublatticeSab<'py>, Vec<Option<f64>>)> {
     // convert PyO3-friendly array types to faer matrices
     let r: Vec<MatRef<C64>> = rotations
         .into_iter()
@@ -183,7 +405,10 @@ pub fn spinwave_calculation_Sab<'py>(
         .map(faer_ext::IntoFaer::into_faer)
         .collect();
 
-    let results = calc_spinwave(r, magnitudes, q_vectors.clone(), c, p, field, true);
+    let bases = sun_bases.as_ref().map(|b| b.par_iter().map(pyo3::Py::get).collect());
+    let d = anisotropy.map(|ds| ds.into_iter().map(|d| d.into_faer().to_owned()).collect());
+
+    let results = calc_spinwave(r, magnitudes, q_vectors.clone(), c, p, field, d, mode, bases, form_factors, epsilon);
     Ok((
         results
             .iter()
@@ -202,16 +427,209 @@ pub fn spinwave_calculation_Sab<'py>(
                     .collect()
             })
             .collect(),
+        results
+            .iter()
+            .map(|result| {
+                result
+                    .sublattice_sab
+                    .iter()
+                    .map(|atom_sab| PyArray2::from_array(py, &atom_sab.as_ref().into_ndarray()))
+                    .collect()
+            })
+            .collect(),
+        results
+            .iter()
+            .map(|result| result.regularization_shift)
+            .collect(),
     ))
 }
 
+/// Calculate the neutron scattering cross-section from Sab tensors (as returned by
+/// `spinwave_calculation_Sab`), applying the detailed-balance Bose occupation factor.
+///
+/// # Parameters
+/// - `Sab`: The Sab tensors for each q-vector, as returned by `spinwave_calculation_Sab`.
+/// - `q_vectors`: The q-vectors `Sab` was computed at.
+/// - `energies`: The mode energies (in meV) for each q-vector, matching `Sab`'s ordering.
+/// - `temperature`: The sample temperature in Kelvin.
+///
+/// # Returns
+/// A list of 1D numpy arrays, each containing S_perp(q, omega) for the corresponding q-vector.
+#[pyfunction]
+pub fn neutron<'py>(
+    py: Python<'py>,
+    Sab: Vec<Vec<PyReadonlyArray2<C64>>>,
+    q_vectors: Vec<Vec<f64>>,
+    energies: Vec<Vec<f64>>,
+    temperature: f64,
+) -> PyResult<SQw<'py>> {
+    let sab: Vec<Vec<Mat<C64>>> = Sab
+        .into_iter()
+        .map(|sab_q| sab_q.into_iter().map(|m| m.into_faer().to_owned()).collect())
+        .collect();
+
+    let results = postprocessing::neutron(sab, q_vectors, energies, temperature);
+    Ok(results.into_iter().map(|r| r.to_pyarray(py)).collect())
+}
+
+/// Calculate the momentum-space Berry curvature `Omega_n^{xy}(q)` of every Bogoliubov band,
+/// via the analytic q-derivative of the dynamical (Bogoliubov) matrix. Only the `Dipole`
+/// spin mode is supported.
+///
+/// # Parameters
+/// - `rotations`, `magnitudes`, `couplings`, `field`, `anisotropy`: same as in `energies`.
+/// - `q_vectors`: the q-vectors to evaluate the Berry curvature at.
+///
+/// # Returns
+/// A list of 1D numpy arrays, each containing `Omega_n^{xy}(q)` for every Bogoliubov band
+/// (in the usual nonincreasing `+/-` mirrored order) at the corresponding q-vector.
+#[pyfunction(signature = (rotations, magnitudes, q_vectors, couplings, field=None, anisotropy=None))]
+pub fn berry_curvature<'py>(
+    py: Python<'py>,
+    rotations: Vec<PyReadonlyArray2<C64>>,
+    magnitudes: Vec<f64>,
+    q_vectors: Vec<Vec<f64>>,
+    couplings: Vec<Py<Coupling>>,
+    field: Option<MagneticField>,
+    anisotropy: Option<Vec<PyReadonlyArray2<C64>>>,
+) -> PyResult<Energies<'py>> {
+    let r: Vec<MatRef<C64>> = rotations
+        .into_iter()
+        .map(faer_ext::IntoFaer::into_faer)
+        .collect();
+
+    let c = couplings.par_iter().map(pyo3::Py::get).collect();
+    let d = anisotropy.map(|ds| ds.into_iter().map(|d| d.into_faer().to_owned()).collect());
+
+    let results = berry::berry_curvature(r, magnitudes, q_vectors, c, field, d);
+    Ok(results
+        .into_iter()
+        .map(|result| result.to_pyarray(py))
+        .collect())
+}
+
+/// Calculate the magnon thermal Hall conductivity `kappa_xy`, integrating the Berry
+/// curvature of every band over the sampled q-grid with the Bose-weighted kernel `c2(rho)`
+/// of Matsumoto & Murakami (2011). Only the `Dipole` spin mode is supported.
+///
+/// # Parameters
+/// - `rotations`, `magnitudes`, `couplings`, `field`, `anisotropy`: same as in `energies`.
+/// - `q_vectors`: the q-points to sample, e.g. a uniform grid over the Brillouin zone.
+/// - `temperature`: the sample temperature in Kelvin.
+/// - `cell_volume`: the real-space unit cell volume, in the same length units as the
+///   reciprocal lattice vectors implicit in `q_vectors`.
+///
+/// # Returns
+/// The thermal Hall conductivity `kappa_xy`.
+#[pyfunction(signature = (rotations, magnitudes, q_vectors, couplings, temperature, cell_volume, field=None, anisotropy=None))]
+#[allow(clippy::too_many_arguments)]
+pub fn thermal_hall(
+    rotations: Vec<PyReadonlyArray2<C64>>,
+    magnitudes: Vec<f64>,
+    q_vectors: Vec<Vec<f64>>,
+    couplings: Vec<Py<Coupling>>,
+    temperature: f64,
+    cell_volume: f64,
+    field: Option<MagneticField>,
+    anisotropy: Option<Vec<PyReadonlyArray2<C64>>>,
+) -> PyResult<f64> {
+    let r: Vec<MatRef<C64>> = rotations
+        .into_iter()
+        .map(faer_ext::IntoFaer::into_faer)
+        .collect();
+
+    let c = couplings.par_iter().map(pyo3::Py::get).collect();
+    let d = anisotropy.map(|ds| ds.into_iter().map(|d| d.into_faer().to_owned()).collect());
+
+    Ok(berry::thermal_hall(r, magnitudes, q_vectors, c, field, d, temperature, cell_volume))
+}
+
+/// Calculate the spin-wave quantum zero-point reduction of the ordered moment at every
+/// site, and the resulting spin-wave-corrected ordered moment, by summing `|V_{i,band}(q)|^2`
+/// of the paraunitary transform over a q-grid. Only the `Dipole` spin mode is supported.
+///
+/// # Parameters
+/// - `rotations`, `magnitudes`, `couplings`, `field`, `anisotropy`: same as in `energies`.
+/// - `q_vectors`: the q-points to sample, e.g. a uniform grid over the Brillouin zone.
+///
+/// # Returns
+/// A tuple of two 1D numpy arrays of length `n_sites`: the quantum zero-point reduction
+/// `dS_i`, and the corrected ordered moment `S_i - dS_i`.
+#[pyfunction(signature = (rotations, magnitudes, q_vectors, couplings, field=None, anisotropy=None))]
+pub fn ordered_moment<'py>(
+    py: Python<'py>,
+    rotations: Vec<PyReadonlyArray2<C64>>,
+    magnitudes: Vec<f64>,
+    q_vectors: Vec<Vec<f64>>,
+    couplings: Vec<Py<Coupling>>,
+    field: Option<MagneticField>,
+    anisotropy: Option<Vec<PyReadonlyArray2<C64>>>,
+) -> PyResult<(Bound<'py, PyArray1<f64>>, Bound<'py, PyArray1<f64>>)> {
+    let r: Vec<MatRef<C64>> = rotations
+        .into_iter()
+        .map(faer_ext::IntoFaer::into_faer)
+        .collect();
+
+    let c = couplings.par_iter().map(pyo3::Py::get).collect();
+    let d = anisotropy.map(|ds| ds.into_iter().map(|d| d.into_faer().to_owned()).collect());
+
+    let (delta_s, corrected) = calc_ordered_moment(r, magnitudes, q_vectors, c, field, d);
+    Ok((delta_s.to_pyarray(py), corrected.to_pyarray(py)))
+}
+
+/// Calculate the analytic magnon group velocity `v_n(q) = dE_n/dq` of every Bogoliubov
+/// band, via `v_n(q) = <n|Sigma dH/dq|n>` in the paraunitary eigenbasis. Degenerate bands
+/// are handled by diagonalizing the velocity operator within the degenerate subspace. Only
+/// the `Dipole` spin mode is supported.
+///
+/// # Parameters
+/// - `rotations`, `magnitudes`, `couplings`, `field`, `anisotropy`: same as in `energies`.
+/// - `q_vectors`: the q-vectors to evaluate the group velocity at.
+///
+/// # Returns
+/// A list of `(2 * n_sites) x 3` numpy arrays, one per q-vector, giving the velocity vector
+/// of every Bogoliubov band (in the same ascending-energy order `energies` returns).
+#[pyfunction(signature = (rotations, magnitudes, q_vectors, couplings, field=None, anisotropy=None))]
+pub fn band_velocities<'py>(
+    py: Python<'py>,
+    rotations: Vec<PyReadonlyArray2<C64>>,
+    magnitudes: Vec<f64>,
+    q_vectors: Vec<Vec<f64>>,
+    couplings: Vec<Py<Coupling>>,
+    field: Option<MagneticField>,
+    anisotropy: Option<Vec<PyReadonlyArray2<C64>>>,
+) -> PyResult<Vec<Bound<'py, PyArray2<f64>>>> {
+    let r: Vec<MatRef<C64>> = rotations
+        .into_iter()
+        .map(faer_ext::IntoFaer::into_faer)
+        .collect();
+
+    let c = couplings.par_iter().map(pyo3::Py::get).collect();
+    let d = anisotropy.map(|ds| ds.into_iter().map(|d| d.into_faer().to_owned()).collect());
+
+    let results = velocities::band_velocities(r, magnitudes, q_vectors, c, field, d);
+    Ok(results
+        .into_iter()
+        .map(|v| PyArray2::from_array(py, &v.as_ref().into_ndarray()))
+        .collect())
+}
+
 /// A Python module implemented in Rust.
 #[pymodule]
 fn rust(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(energies, m)?)?;
+    m.add_function(wrap_pyfunction!(magnon_dos, m)?)?;
     m.add_function(wrap_pyfunction!(spinwave_calculation, m)?)?;
     m.add_function(wrap_pyfunction!(spinwave_calculation_Sab, m)?)?;
+    m.add_function(wrap_pyfunction!(neutron, m)?)?;
+    m.add_function(wrap_pyfunction!(berry_curvature, m)?)?;
+    m.add_function(wrap_pyfunction!(thermal_hall, m)?)?;
+    m.add_function(wrap_pyfunction!(ordered_moment, m)?)?;
+    m.add_function(wrap_pyfunction!(band_velocities, m)?)?;
     m.add_class::<Coupling>()?;
     m.add_class::<MagneticField>()?;
+    m.add_class::<SpinMode>()?;
+    m.add_class::<SUNBasis>()?;
+    m.add_class::<FormFactor>()?;
     Ok(())
 }
@@ -2,6 +2,7 @@
 use faer::{Col, Mat};
 use rayon::prelude::*;
 
+use crate::constants::K_B;
 use crate::utils::component_mul;
 use crate::C64;
 
@@ -10,19 +11,33 @@ use crate::C64;
 /// # Parameters
 /// - `Sab`: The correlation tensor returned by `calc_spinwave`.
 /// This is a vector over q, where each element is a vector over (non-zero) omega where each
-/// element is a 3x3 matrix of complex numbers representing S^alpha,beta(q, omega).
+/// element is a 3x3 matrix of complex numbers representing S^alpha,beta(q, omega). If the
+/// calculation used per-site `FormFactor` coefficients, the magnetic form factor is already
+/// folded into `Sab` (see `calc_sab_blocks`).
 /// - `q_vectors`: The list of q-vectors over which `Sab` is given.
+/// - `energies`: The mode energies (in meV), one vector per q-vector, matching `Sab`'s
+///   omega ordering.
+/// - `temperature`: The sample temperature in Kelvin, used for the detailed-balance Bose
+///   occupation factor `n(omega) + 1`. A non-positive temperature is treated as T = 0.
 ///
 /// # Returns
 /// A vector over q, where each element is a vector over (non-zero) omega where each element is
 /// the neutron scattering cross-section S_perp(q, omega).
-pub fn neutron(Sab: Vec<Vec<Mat<C64>>>, q_vectors: Vec<Vec<f64>>) -> Vec<Vec<f64>> {
+pub fn neutron(
+    Sab: Vec<Vec<Mat<C64>>>,
+    q_vectors: Vec<Vec<f64>>,
+    energies: Vec<Vec<f64>>,
+    temperature: f64,
+) -> Vec<Vec<f64>> {
     Sab.par_iter()
         .zip(q_vectors)
-        .map(|(Sab_q, q)| {
+        .zip(energies)
+        .map(|((Sab_q, q), energies_q)| {
             Sperp_single_q(
                 Sab_q,
                 Col::<C64>::from_iter(q.iter().map(C64::from)),
+                &energies_q,
+                temperature,
             )
         })
         .collect()
@@ -34,11 +49,18 @@ pub fn neutron(Sab: Vec<Vec<Mat<C64>>>, q_vectors: Vec<Vec<f64>>) -> Vec<Vec<f64
 /// - `Sab_q`: The correlation tensor for a single q-vector, given as a vector over (non-zero) omega
 /// where each element is a 3x3 matrix of complex numbers representing S^alpha,beta(q, omega).
 /// - `wavevector`: The q-vector as a column vector of complex numbers.
+/// - `energies_q`: The mode energies (in meV) for this q-vector, matching `Sab_q`'s ordering.
+/// - `temperature`: The sample temperature in Kelvin.
 ///
 /// # Returns
 /// A vector over (non-zero) omega where each element is the neutron scattering cross-section
 /// S_perp(q, omega).
-fn Sperp_single_q(Sab_q: &[Mat<C64>], wavevector: Col<C64>) -> Vec<f64> {
+fn Sperp_single_q(
+    Sab_q: &[Mat<C64>],
+    wavevector: Col<C64>,
+    energies_q: &[f64],
+    temperature: f64,
+) -> Vec<f64> {
     let mut norm_q = wavevector.as_ref() / wavevector.norm_l2();
     if norm_q.has_nan() {
         norm_q = Col::<C64>::from_iter(vec![C64::from(0.0), C64::from(0.0), C64::from(0.0)]);
@@ -46,6 +68,19 @@ fn Sperp_single_q(Sab_q: &[Mat<C64>], wavevector: Col<C64>) -> Vec<f64> {
     let perp_factor = Mat::<C64>::identity(3, 3) - (norm_q.as_ref() * norm_q.adjoint());
     Sab_q
         .iter()
-        .map(|Sab_qw| -> f64 { component_mul(Sab_qw, &perp_factor).sum().re })
+        .zip(energies_q)
+        .map(|(Sab_qw, &omega)| -> f64 {
+            component_mul(Sab_qw, &perp_factor).sum().re * bose_factor(omega, temperature)
+        })
         .collect()
 }
+
+/// Detailed-balance Bose occupation factor `n(omega) + 1`, with
+/// `n(omega) = 1 / (exp(omega / (k_B * T)) - 1)`. Reduces to 1 in the `T -> 0` limit.
+fn bose_factor(omega: f64, temperature: f64) -> f64 {
+    if temperature <= 0.0 || omega <= 0.0 {
+        return 1.0;
+    }
+    let n = 1. / ((omega / (K_B * temperature)).exp() - 1.);
+    n + 1.
+}
@@ -6,15 +6,68 @@ use rayon::iter::{IntoParallelIterator, ParallelIterator};
 
 use crate::constants::{J, MU_B};
 use crate::utils::*;
-use crate::{Coupling, MagneticField, C64};
+use crate::{Coupling, FormFactor, MagneticField, SUNBasis, SpinMode, C64};
+
+/// Calculate the biquadratic renormalization factor `2 * K * (classical S_i . S_j)` for a
+/// coupling, added to the bilinear A/B/C contributions of that same coupling as a separate,
+/// identity-structured term (not a multiplicative scaling of the M-structured bilinear term,
+/// which would incorrectly inherit M's structure/normalization). Returns zero for couplings
+/// with no biquadratic term.
+fn biquadratic_factor(c: &Coupling, magnitudes: &[f64], etas: &[ColRef<C64>]) -> C64 {
+    match c.biquadratic {
+        Some(k) => {
+            // classical value of S_i . S_j = S_i S_j (eta_i . eta_j)
+            let classical_dot = etas[c.index1].transpose() * etas[c.index2];
+            let classical_SiSj = C64::from(magnitudes[c.index1] * magnitudes[c.index2]);
+            2. * k * classical_SiSj * classical_dot
+        }
+        None => C64::from(0.),
+    }
+}
+
+/// Calculate the diagonal A, B and C contributions of the single-ion anisotropy term
+/// `S_i^T D_i S_i` for every site, given its (symmetrized) 3x3 anisotropy tensor `D_i`.
+///
+/// Returns, for each site, the `(A diagonal, B diagonal, C diagonal)` additions.
+fn anisotropy_terms(
+    anisotropy: &[Mat<C64>],
+    magnitudes: &[f64],
+    z: &[Col<C64>],
+    etas: &[ColRef<C64>],
+) -> Vec<(C64, C64, C64)> {
+    anisotropy
+        .iter()
+        .enumerate()
+        .map(|(i, d)| {
+            // D_i must be symmetric for the resulting block matrix to stay Hermitian
+            let d_sym = (d.clone() + d.transpose()) * 0.5;
+            let s_i = C64::from(magnitudes[i]);
+            let eta_term = etas[i].transpose() * d_sym.as_ref() * etas[i];
+            let z_term = z[i].transpose() * d_sym.as_ref() * z[i].conjugate();
+
+            let a_add = s_i * z_term - 2. * s_i * eta_term;
+            let b_add = s_i * (z[i].transpose() * d_sym.as_ref() * z[i].as_ref());
+            let c_add = 2. * s_i * eta_term;
+            (a_add, b_add, c_add)
+        })
+        .collect()
+}
 
 /// Calculate the q-independent components of the calculation.
-fn calc_q_independent(
+pub(crate) fn calc_q_independent(
     rotations: Vec<MatRef<C64>>,
     magnitudes: Vec<f64>,
     couplings: &Vec<&Coupling>,
     field: Option<MagneticField>,
-) -> (Mat<C64>, Vec<Col<C64>>, Mat<C64>, Option<Vec<C64>>) {
+    anisotropy: &Option<Vec<Mat<C64>>>,
+) -> (
+    Mat<C64>,
+    Vec<Col<C64>>,
+    Mat<C64>,
+    Option<Vec<C64>>,
+    Vec<C64>,
+    Option<Vec<(C64, C64)>>,
+) {
     let n_sites = rotations.len();
 
     // decompose rotation matrices
@@ -27,11 +80,23 @@ fn calc_q_independent(
     let root_mags = Col::<C64>::from_iter(magnitudes.iter().map(|x| C64::from((0.5 * x).sqrt())));
     let spin_coefficients = root_mags.clone() * root_mags.transpose();
 
+    // biquadratic renormalization factor per coupling, reused both here (for C) and in
+    // `calc_sqrt_hamiltonian` (for the q-dependent A/B renormalization)
+    let biquadratic_factors: Vec<C64> = couplings
+        .iter()
+        .map(|c| biquadratic_factor(c, &magnitudes, &etas))
+        .collect();
+
     // create matrix C of Hamiltonian which is q-independent
     let mut C = Mat::<C64>::zeros(n_sites, n_sites);
-    for c in couplings {
+    for (c, &bq) in couplings.iter().zip(&biquadratic_factors) {
+        // the biquadratic term renormalizes the bilinear M-structured contribution by an
+        // *additive*, identity-structured `bq * (eta_i . eta_j)` term (not a multiplicative
+        // `1 + bq` scaling of the M-structured term, which would incorrectly pick up M's
+        // structure/normalization)
         C[(c.index2, c.index2)] += spin_coefficients[(c.index2, c.index2)]
-            * (etas[c.index1].transpose() * c.matrix.as_ref() * etas[c.index2]);
+            * ((etas[c.index1].transpose() * c.matrix.as_ref() * etas[c.index2])
+                + bq * (etas[c.index1].transpose() * etas[c.index2]));
     }
     C *= 2.;
 
@@ -47,36 +112,221 @@ fn calc_q_independent(
         ),
         None => None,
     };
-    (C, z, spin_coefficients, Az)
+
+    // single-ion anisotropy contributes a diagonal A/B term (returned for
+    // `calc_sqrt_hamiltonian` to add alongside `Az`) and a constant C diagonal shift
+    let anisotropy_ab: Option<Vec<(C64, C64)>> = anisotropy.as_ref().map(|d| {
+        let terms = anisotropy_terms(d, &magnitudes, &z, &etas);
+        for (i, &(_, _, c_add)) in terms.iter().enumerate() {
+            C[(i, i)] += c_add;
+        }
+        terms.into_iter().map(|(a, b, _)| (a, b)).collect()
+    });
+
+    (C, z, spin_coefficients, Az, biquadratic_factors, anisotropy_ab)
 }
 
-/// Calculate the square root of the Hamiltonian.
-#[inline(always)]
-fn calc_sqrt_hamiltonian(
-    q: Col<f64>,
-    C: &Mat<C64>,
+/// Result of diagonalizing the bosonic Bogoliubov Hamiltonian at a single q-point.
+pub(crate) enum BogoliubovSolution {
+    /// Colpa's positive-definite case: the Cholesky square root `K` of the Hamiltonian,
+    /// from which the bosonic eigenproblem is solved downstream as `K^dagger g K`.
+    Cholesky(Mat<C64>),
+    /// Regularized fallback for Hamiltonians that are not positive definite, used in
+    /// preference to `General` when an `epsilon` regularization is given: an LDL^T-derived
+    /// approximate square root `K`, usable exactly like `Cholesky`'s, plus the largest
+    /// regularization shift applied to `D`'s diagonal (see [`regularized_ldl_sqrt`]).
+    Regularized { sqrt_hamiltonian: Mat<C64>, shift: f64 },
+    /// Fallback for Hamiltonians that are not positive definite (e.g. at Goldstone/zero
+    /// modes): the magnon energies and paraunitary transform `T`, obtained directly from
+    /// the non-Hermitian eigenproblem of `g * H` via [`eigs::eigs_general`]. `unstable` is
+    /// the largest `|Im(eigenvalue)|` among the physical modes if any is non-negligible
+    /// (dynamically unstable, see [`solve_general_bogoliubov`]), or `None` otherwise.
+    General { energies: Vec<f64>, T: Mat<C64>, unstable: Option<f64> },
+}
+
+/// Imaginary part magnitude above which a `g * H` eigenvalue is considered a dynamical
+/// instability rather than numerical noise.
+const INSTABILITY_TOL: f64 = 1e-8;
+
+/// Diagonalize the non-Hermitian matrix `g * H`, where `g = diag(I_n, -I_n)` is the
+/// bosonic commutation metric, as the fallback path for Hamiltonians that are not
+/// positive definite. Eigenvectors are normalized so that `v^dagger g v = +1` for the
+/// positive-energy branch and `-1` for its negative-energy partner, giving the
+/// paraunitary transform `T` in the same `2n x 2n` layout expected by `calc_sab_blocks`.
+/// Modes with non-negligible imaginary eigenvalue are dynamically unstable; their energy
+/// is returned as-is (real part) rather than panicking.
+fn solve_general_bogoliubov(hamiltonian: &Mat<C64>, n_sites: usize) -> BogoliubovSolution {
+    let dim = 2 * n_sites;
+
+    let mut gH = hamiltonian.clone();
+    let mut negative_half = gH.submatrix_mut(n_sites, 0, n_sites, dim);
+    negative_half *= -1.;
+
+    let gh_na = nalgebra::DMatrix::<C64>::from_fn(dim, dim, |r, c| gH[(r, c)]);
+    let (eigvals, eigvecs) = crate::eigs::eigs_general(gh_na)
+        .expect("Could not solve the non-Hermitian fallback Bogoliubov problem.");
+
+    let mut g = Mat::<C64>::zeros(dim, dim);
+    for i in 0..n_sites {
+        g[(i, i)] = C64::from(1.);
+        g[(n_sites + i, n_sites + i)] = C64::from(-1.);
+    }
+
+    // the spectrum of g*H comes in +/- pairs; take the n_sites modes with (approximately)
+    // non-negative real part as the physical magnon energies, and the n_sites modes with
+    // the most negative real part as their time-reversed partners
+    let mut order: Vec<usize> = (0..dim).collect();
+    order.sort_by(|&a, &b| eigvals[b].re.partial_cmp(&eigvals[a].re).unwrap());
+    let (positive, negative) = order.split_at(n_sites);
+
+    let mut T = Mat::<C64>::zeros(dim, dim);
+    let mut energies = Vec::with_capacity(n_sites);
+    let mut unstable: Option<f64> = None;
+    for (branch, indices) in [(0, positive), (n_sites, negative)] {
+        for (col_offset, &idx) in indices.iter().enumerate() {
+            let v = Col::<C64>::from_iter((0..dim).map(|r| eigvecs[(r, idx)]));
+            let norm = (v.adjoint() * g.as_ref() * v.as_ref()).re;
+            let scale = C64::from(1. / norm.abs().sqrt());
+            for r in 0..dim {
+                T[(r, branch + col_offset)] = v[r] * scale;
+            }
+            if branch == 0 {
+                let energy = eigvals[idx];
+                energies.push(energy.re);
+                if energy.im.abs() > INSTABILITY_TOL {
+                    unstable = Some(f64::max(unstable.unwrap_or(0.), energy.im.abs()));
+                }
+            }
+        }
+    }
+
+    BogoliubovSolution::General { energies, T, unstable }
+}
+
+/// Regularized LDL^T fallback for Hamiltonians that are not positive definite, used in
+/// preference to [`solve_general_bogoliubov`] when an `epsilon` regularization is given:
+/// factorize the complex-Hermitian `H = P L D L^dagger P^T` via faer's pivoted `lblt`
+/// (see [`calc_sqrt_hamiltonian_sun`]'s fallback for the same idiom), shift any diagonal
+/// entry of `D` whose real part falls below `epsilon` up to `epsilon` (Tikhonov-style), and
+/// reconstruct `K = P^-1 * L * sqrt(D_reg)` so the usual Colpa diagonalization can proceed
+/// exactly as it would for a genuine Cholesky factor. Returns `K` and the largest shift
+/// applied to `D` (zero if no entry needed regularizing), so the caller can flag this
+/// q-point's introduced error.
+fn regularized_ldl_sqrt(hamiltonian: &Mat<C64>, epsilon: f64) -> (Mat<C64>, f64) {
+    let dim = hamiltonian.nrows();
+    let ldl = hamiltonian.clone().lblt(Side::Lower);
+    let l = ldl.L();
+    let d = ldl.B_diag().column_vector();
+
+    let mut max_shift = 0.;
+    let mut sqrt_d = Col::<C64>::zeros(dim);
+    for i in 0..dim {
+        let d_i = d[i].re;
+        let d_reg = d_i.max(epsilon);
+        max_shift = f64::max(max_shift, d_reg - d_i);
+        sqrt_d[i] = C64::from(d_reg.sqrt());
+    }
+
+    (ldl.P().inverse() * l * sqrt_d.as_diagonal(), max_shift)
+}
+
+/// Build the bilinear + biquadratic contributions to `A` and `B` from the coupling list,
+/// weighting each coupling's inter-site phase factor `exp(2*pi*i*q.d)` by `weight(c)`.
+/// Passing `|_| C64::from(1.)` gives the Hamiltonian's `A`/`B` (see [`calc_sqrt_hamiltonian`]);
+/// passing `|c| (2*J*PI) * c.inter_site_vector[direction]` gives their analytic derivative
+/// with respect to `q[direction]` (see [`calc_AB_gradient`]).
+fn calc_AB_weighted(
+    q: &Col<f64>,
     n_sites: usize,
     z: &[Col<C64>],
     spin_coefficients: &Mat<C64>,
     couplings: &[&Coupling],
-    Az: &Option<Vec<C64>>,
-) -> Mat<C64> {
-    // create A and B matrices for the Hamiltonian
-
+    biquadratic_factors: &[C64],
+    weight: impl Fn(&Coupling) -> C64,
+) -> (Mat<C64>, Mat<C64>) {
     let mut A = Mat::<C64>::zeros(n_sites, n_sites);
     let mut B = Mat::<C64>::zeros(n_sites, n_sites);
 
-    for c in couplings {
-        let phase_factor = ((2. * J * PI) * (q.transpose() * c.inter_site_vector.as_ref())).exp();
+    for (c, &bq) in couplings.iter().zip(biquadratic_factors) {
+        let phase_factor =
+            ((2. * J * PI) * (q.transpose() * c.inter_site_vector.as_ref())).exp() * weight(c);
         let (i, j) = (c.index1, c.index2);
 
-        // contributions to A and B from this coupling
-        A[(i, j)] += (z[i].transpose() * c.matrix.as_ref() * z[j].conjugate()) * phase_factor;
-        B[(i, j)] += (z[i].transpose() * c.matrix.as_ref() * z[j].as_ref()) * phase_factor;
+        // bilinear contribution to A and B from this coupling, plus the biquadratic
+        // term's additive, identity-structured renormalization `bq = 2 * K * (classical
+        // S_i . S_j)` (zero if no K is set) built from the outer product of the linear
+        // (z) coupling vectors rather than the M-structured exchange matrix itself
+        A[(i, j)] += ((z[i].transpose() * c.matrix.as_ref() * z[j].conjugate())
+            + bq * (z[i].transpose() * z[j].conjugate()))
+            * phase_factor;
+        B[(i, j)] += ((z[i].transpose() * c.matrix.as_ref() * z[j].as_ref())
+            + bq * (z[i].transpose() * z[j].as_ref()))
+            * phase_factor;
+
+        // rank-one contribution K * (linear delta)^2 from the biquadratic term, built from
+        // the outer product of the linear (z) coupling vectors rather than the exchange
+        // matrix itself
+        if let Some(k) = c.biquadratic {
+            A[(i, j)] += k * (z[i].transpose() * z[j].conjugate()) * phase_factor;
+            B[(i, j)] += k * (z[i].transpose() * z[j].as_ref()) * phase_factor;
+        }
     }
 
-    A = component_mul(&A, spin_coefficients);
-    B = component_mul(&B, spin_coefficients);
+    (
+        component_mul(&A, spin_coefficients),
+        component_mul(&B, spin_coefficients),
+    )
+}
+
+/// Analytic derivative of [`calc_AB_weighted`]'s `A`/`B` with respect to `q[direction]`
+/// (`direction` 0, 1, 2 for x, y, z), obtained by differentiating each coupling's
+/// `exp(2*pi*i*q.d)` phase factor. `Az`/anisotropy terms are q-independent and so do not
+/// contribute (their derivative is zero), matching `C` in [`calc_sqrt_hamiltonian`].
+pub(crate) fn calc_AB_gradient(
+    q: &Col<f64>,
+    direction: usize,
+    n_sites: usize,
+    z: &[Col<C64>],
+    spin_coefficients: &Mat<C64>,
+    couplings: &[&Coupling],
+    biquadratic_factors: &[C64],
+) -> (Mat<C64>, Mat<C64>) {
+    calc_AB_weighted(
+        q,
+        n_sites,
+        z,
+        spin_coefficients,
+        couplings,
+        biquadratic_factors,
+        |c| (2. * J * PI) * c.inter_site_vector[direction],
+    )
+}
+
+/// Calculate the square root of the Hamiltonian.
+#[inline(always)]
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn calc_sqrt_hamiltonian(
+    q: Col<f64>,
+    C: &Mat<C64>,
+    n_sites: usize,
+    z: &[Col<C64>],
+    spin_coefficients: &Mat<C64>,
+    couplings: &[&Coupling],
+    Az: &Option<Vec<C64>>,
+    biquadratic_factors: &[C64],
+    anisotropy_ab: &Option<Vec<(C64, C64)>>,
+    epsilon: Option<f64>,
+) -> BogoliubovSolution {
+    // create A and B matrices for the Hamiltonian
+    let (mut A, mut B) = calc_AB_weighted(
+        &q,
+        n_sites,
+        z,
+        spin_coefficients,
+        couplings,
+        biquadratic_factors,
+        |_| C64::from(1.),
+    );
 
     // slightly convoluted way to add to the diagonal of A because adding a Diag to a Mat
     // isn't implemented by `faer` yet (missed out, apparently!)
@@ -86,51 +336,134 @@ fn calc_sqrt_hamiltonian(
         }
     }
 
+    // single-ion anisotropy diagonal contributions to A and B, computed once in
+    // `calc_q_independent` (they do not depend on q)
+    if let Some(terms) = anisotropy_ab {
+        for (i, &(a_add, b_add)) in terms.iter().enumerate() {
+            A[(i, i)] += a_add;
+            B[(i, i)] += b_add;
+        }
+    }
+
     let A_minus_C: Mat<C64> = A.clone() - C;
     let A_conj_minus_C: Mat<C64> = A.adjoint() - C;
     let B_adj = B.adjoint().to_owned();
 
     let hamiltonian: Mat<C64> = block_matrix(&A_minus_C, &B, &B_adj, &A_conj_minus_C);
 
-    // take square root of Hamiltonian using Cholesky if possible; if this fails,
-    // use the LDL (Bunch-Kaufmann) decomposition instead and take sqrt(H) = L * sqrt(D)
-    let sqrt_hamiltonian = {
-        if let Ok(chol) = hamiltonian.clone().llt(Side::Lower) {
-            chol.L().to_owned()
-        } else {
-            let ldl = hamiltonian.lblt(Side::Lower);
-            let l = ldl.L();
-            let d = ldl.B_diag().column_vector(); // we're ignoring off-diagonals... this may be
-                                                  // dangerous
-
-            // we use the zip and unzip to map over d and allocate to sqrt_d
-            let mut sqrt_d = Col::<C64>::zeros(d.nrows());
-            zip!(&mut sqrt_d, d).for_each(|unzip!(sqd, v)| *sqd = v.sqrt());
-
-            // need to apply permutations: in Python scipy does this for you
-            ldl.P().inverse() * l * sqrt_d.as_diagonal()
-        }
-    };
-    sqrt_hamiltonian
+    // take the square root of the Hamiltonian using Cholesky if it's positive definite;
+    // otherwise (e.g. at Goldstone/zero modes) fall back to a regularized LDL^T square
+    // root if an `epsilon` was given, or to diagonalizing the non-Hermitian matrix g*H
+    // directly otherwise
+    if let Ok(chol) = hamiltonian.clone().llt(Side::Lower) {
+        BogoliubovSolution::Cholesky(chol.L().to_owned())
+    } else if let Some(eps) = epsilon {
+        let (sqrt_hamiltonian, shift) = regularized_ldl_sqrt(&hamiltonian, eps);
+        BogoliubovSolution::Regularized { sqrt_hamiltonian, shift }
+    } else {
+        solve_general_bogoliubov(&hamiltonian, n_sites)
+    }
 }
 
+/// Calculate the magnon energies for every q-vector.
+///
+/// `epsilon`, if given, enables the regularized LDL^T fallback (see
+/// [`regularized_ldl_sqrt`]) for q-points where the Bogoliubov Hamiltonian is not positive
+/// definite (e.g. at Goldstone/zero modes), in preference to the non-Hermitian eigensolver
+/// fallback. Each q-point's result is paired with the largest regularization shift applied,
+/// or `None` if the Hamiltonian was positive definite (or the non-Hermitian fallback was
+/// used instead, i.e. `epsilon` was not given), and with the largest `|Im(eigenvalue)|`
+/// found by the non-Hermitian fallback if it flagged a dynamically unstable mode, or `None`
+/// otherwise (see [`solve_general_bogoliubov`]).
+///
+/// In [`SpinMode::GeneralizedSUN`] mode, `field`, `anisotropy` and `epsilon` are not yet
+/// supported (single-ion anisotropy is instead captured directly by the per-site
+/// [`SUNBasis`]'s choice of local basis, and the regularized LDL^T fallback has no SU(N)
+/// analog yet) and must be `None`.
+#[allow(clippy::too_many_arguments)]
 pub fn calc_energies(
     rotations: Vec<MatRef<C64>>,
     magnitudes: Vec<f64>,
     q_vectors: Vec<Vec<f64>>,
     couplings: Vec<&Coupling>,
     field: Option<MagneticField>,
-) -> Vec<Vec<f64>> {
+    anisotropy: Option<Vec<Mat<C64>>>,
+    mode: SpinMode,
+    sun_bases: Option<Vec<&SUNBasis>>,
+    epsilon: Option<f64>,
+) -> Vec<(Vec<f64>, Option<f64>, Option<f64>)> {
+    match mode {
+        SpinMode::Dipole => {
+            let n_sites = rotations.len();
+
+            let (C, z, spin_coefficients, Az, biquadratic_factors, anisotropy_ab) =
+                calc_q_independent(rotations, magnitudes, &couplings, field, &anisotropy);
+
+            // now perform the calculation for each q-vector in parallel
+            q_vectors
+                .into_par_iter()
+                .map(|q| {
+                    energies_single_q(
+                        Col::from_iter(q),
+                        &C,
+                        n_sites,
+                        &z,
+                        &spin_coefficients,
+                        &couplings,
+                        &Az,
+                        &biquadratic_factors,
+                        &anisotropy_ab,
+                        epsilon,
+                    )
+                })
+                .collect()
+        }
+        SpinMode::GeneralizedSUN => {
+            assert!(
+                field.is_none() && anisotropy.is_none() && epsilon.is_none(),
+                "field, anisotropy and epsilon are not yet supported for GeneralizedSUN mode; \
+                 encode single-ion anisotropy directly in the per-site SUNBasis instead"
+            );
+            let bases = sun_bases.expect("SU(N) bases must be provided when mode is GeneralizedSUN");
+            calc_energies_sun(bases, q_vectors, couplings)
+                .into_iter()
+                .map(|e| (e, None, None))
+                .collect()
+        }
+    }
+}
+
+/// Calculate the spin-wave quantum zero-point reduction of the ordered moment at every
+/// site, `dS_i = (1 / N_q) * sum_q sum_band |V_{i,band}(q)|^2`, where `V` is the anomalous
+/// (particle-hole mixing) block of the paraunitary transform `T` used throughout this
+/// module, and the corrected ordered moment `S_i - dS_i`.
+///
+/// Only the [`SpinMode::Dipole`] mode is supported, since the generalized SU(N) expansion
+/// has no single classical ordered moment per site to correct.
+///
+/// # Returns
+/// A tuple of `(dS, corrected_moments)`, each a `Vec<f64>` of length `n_sites`.
+pub fn ordered_moment(
+    rotations: Vec<MatRef<C64>>,
+    magnitudes: Vec<f64>,
+    q_vectors: Vec<Vec<f64>>,
+    couplings: Vec<&Coupling>,
+    field: Option<MagneticField>,
+    anisotropy: Option<Vec<Mat<C64>>>,
+) -> (Vec<f64>, Vec<f64>) {
     let n_sites = rotations.len();
+    let n_q = q_vectors.len() as f64;
 
-    let (C, z, spin_coefficients, Az) =
-        calc_q_independent(rotations, magnitudes, &couplings, field);
+    let (C, z, spin_coefficients, Az, biquadratic_factors, anisotropy_ab) =
+        calc_q_independent(rotations, magnitudes.clone(), &couplings, field, &anisotropy);
 
-    // now perform the calculation for each q-vector in parallel
-    q_vectors
+    // for each q, |V_{i,band}|^2 summed over the n_sites physical bands (the mirrored
+    // negative-energy branch is the time-reversed partner and double-counts the same
+    // zero-point reduction, so only the first n_sites columns of T are used)
+    let per_q: Vec<Vec<f64>> = q_vectors
         .into_par_iter()
         .map(|q| {
-            energies_single_q(
+            let solution = calc_sqrt_hamiltonian(
                 Col::from_iter(q),
                 &C,
                 n_sites,
@@ -138,35 +471,157 @@ pub fn calc_energies(
                 &spin_coefficients,
                 &couplings,
                 &Az,
-            )
+                &biquadratic_factors,
+                &anisotropy_ab,
+                None,
+            );
+            let (_, T, _, _) = bogoliubov_modes(solution, n_sites);
+
+            (0..n_sites)
+                .map(|i| {
+                    (0..n_sites)
+                        .map(|band| T[(n_sites + i, band)].norm_sqr())
+                        .sum::<f64>()
+                })
+                .collect()
+        })
+        .collect();
+
+    let mut delta_s = vec![0.; n_sites];
+    for site_vals in &per_q {
+        for (acc, &v) in delta_s.iter_mut().zip(site_vals) {
+            *acc += v;
+        }
+    }
+    delta_s.iter_mut().for_each(|ds| *ds /= n_q);
+
+    let corrected_moments: Vec<f64> = delta_s
+        .iter()
+        .zip(&magnitudes)
+        .map(|(ds, s)| s - ds)
+        .collect();
+
+    (delta_s, corrected_moments)
+}
+
+/// Number of boson flavors per site (N - 1) for the generalized SU(N) expansion.
+/// All sites are assumed to share the same local Hilbert space dimension N = 2S+1.
+fn sun_flavors(bases: &[&SUNBasis]) -> usize {
+    bases[0].linear_matrix_elements().ncols()
+}
+
+/// Calculate the energies (eigenvalues of the Bogoliubov Hamiltonian) for the
+/// generalized SU(N) flavor-wave expansion, for every q-vector.
+///
+/// This is the N > 2 analog of [`calc_energies`]: instead of a single Holstein-Primakoff
+/// boson per site, each site carries N - 1 bosonic flavors, and the per-site `z`/`eta`
+/// column vectors of the dipole mode are replaced by the 3 x (N - 1) matrix of
+/// `<m|S^alpha|0>` matrix elements stored on each [`SUNBasis`]. The dipole mode is
+/// recovered as the special case N = 2 (a single flavor per site).
+pub fn calc_energies_sun(
+    bases: Vec<&SUNBasis>,
+    q_vectors: Vec<Vec<f64>>,
+    couplings: Vec<&Coupling>,
+) -> Vec<Vec<f64>> {
+    let n_sites = bases.len();
+    let n_flavors = sun_flavors(&bases);
+
+    q_vectors
+        .into_par_iter()
+        .map(|q| {
+            let sqrt_hamiltonian =
+                calc_sqrt_hamiltonian_sun(Col::from_iter(q), &bases, &couplings, n_sites, n_flavors);
+            let dim = n_sites * n_flavors;
+            let mut shc: Mat<C64> = sqrt_hamiltonian.clone();
+            let mut negative_half = shc.submatrix_mut(dim, 0, dim, 2 * dim);
+            negative_half *= -1.;
+
+            (sqrt_hamiltonian.adjoint() * shc)
+                .self_adjoint_eigenvalues(Side::Lower)
+                .expect("Could not calculate eigendecomposition of the SU(N) Hamiltonian.")
         })
         .collect()
 }
 
-/// Calculate energies (eigenvalues of the Hamiltonian) for a single q-value
-fn energies_single_q(
+/// Build the square root of the generalized SU(N) Bogoliubov Hamiltonian at a single q-point.
+///
+/// Each coupling contributes an (N - 1) x (N - 1) block to `A` and `B` built from the
+/// `linear_matrix_elements()` of the two sites it connects, generalizing the scalar
+/// `z[i]^T * matrix * z[j]` contraction used in [`calc_sqrt_hamiltonian`], accumulated in
+/// case several couplings share the same ordered site pair (e.g. multi-path exchange).
+///
+/// Each coupling also contributes an on-site diagonal correction, the SU(N) analog of the
+/// dipole mode's `A - C` subtraction: expanding `<0|S_i|0> . matrix . <0|S_j|0>` (the
+/// classical bond energy) to quadratic order in the bosons via the coherent-state
+/// normalization `S_i^alpha ~ <0|S_i^alpha|0> * (1 - n_i) + ...` produces a
+/// `-classical_bond_energy * (n_i + n_j)` correction, diagonal in flavor space, on top of
+/// the (already-accounted-for) constant classical energy itself. This reduces to the
+/// dipole mode's `C` for N = 2.
+fn calc_sqrt_hamiltonian_sun(
     q: Col<f64>,
-    C: &Mat<C64>,
-    n_sites: usize,
-    z: &[Col<C64>],
-    spin_coefficients: &Mat<C64>,
+    bases: &[&SUNBasis],
     couplings: &[&Coupling],
-    Az: &Option<Vec<C64>>,
-) -> Vec<f64> {
-    let sqrt_hamiltonian =
-        calc_sqrt_hamiltonian(q, C, n_sites, z, spin_coefficients, couplings, Az);
-    // 'shc' is "square root of Hamiltonian with commutation"`
-    // We need to enforce the bosonic commutation properties, we do this
-    // by finding the 'square root' of the matrix (i.e. finding K such that KK^dagger = H)
-    // and then negating the second half.
-    //
-    // In matrix form we do
-    //
-    //     M = K^dagger g K
-    //
-    // where g is a diagonal matrix of length 2n, with the first n entries being 1, and the
-    // remaining entries being -1.
-    // We do this by just multiplying the >n_sites rows of shc to get g*K
+    n_sites: usize,
+    n_flavors: usize,
+) -> Mat<C64> {
+    let dim = n_sites * n_flavors;
+    let mut A = Mat::<C64>::zeros(dim, dim);
+    let mut B = Mat::<C64>::zeros(dim, dim);
+    let mut c_diag = vec![C64::from(0.); n_sites];
+
+    for c in couplings {
+        let phase_factor = ((2. * J * PI) * (q.transpose() * c.inter_site_vector.as_ref())).exp();
+        let (i, j) = (c.index1, c.index2);
+        let li = bases[i].linear_matrix_elements();
+        let lj = bases[j].linear_matrix_elements();
+
+        // particle-conserving block: L_i^T * matrix * conj(L_j)
+        let a_block = (li.transpose() * c.matrix.as_ref() * lj.conjugate()) * phase_factor;
+        // particle-nonconserving block: L_i^T * matrix * L_j
+        let b_block = (li.transpose() * c.matrix.as_ref() * lj.as_ref()) * phase_factor;
+
+        let mut a_dest = A.submatrix_mut(i * n_flavors, j * n_flavors, n_flavors, n_flavors);
+        zip!(&mut a_dest, &a_block).for_each(|unzip!(dest, add)| *dest += add);
+        let mut b_dest = B.submatrix_mut(i * n_flavors, j * n_flavors, n_flavors, n_flavors);
+        zip!(&mut b_dest, &b_block).for_each(|unzip!(dest, add)| *dest += add);
+
+        // classical bond energy <0|S_i|0> . matrix . <0|S_j|0>, q-independent (phase
+        // factor is 1 at the classical/zero-boson order), assigned to site j's diagonal
+        // exactly like `calc_q_independent`'s `C`
+        c_diag[j] += bases[i].expectation().transpose() * c.matrix.as_ref() * bases[j].expectation();
+    }
+
+    // subtract the on-site diagonal correction from A (and, conjugated, from A^dagger)
+    // before forming the block Hamiltonian, mirroring `calc_sqrt_hamiltonian`'s `A - C`
+    let mut A_minus_C = A.clone();
+    let mut A_conj_minus_C = A.adjoint().to_owned();
+    for site in 0..n_sites {
+        for f in 0..n_flavors {
+            let idx = site * n_flavors + f;
+            A_minus_C[(idx, idx)] -= c_diag[site];
+            A_conj_minus_C[(idx, idx)] -= c_diag[site];
+        }
+    }
+
+    let B_adj = B.adjoint().to_owned();
+    let hamiltonian: Mat<C64> = block_matrix(&A_minus_C, &B, &B_adj, &A_conj_minus_C);
+
+    if let Ok(chol) = hamiltonian.clone().llt(Side::Lower) {
+        chol.L().to_owned()
+    } else {
+        let ldl = hamiltonian.lblt(Side::Lower);
+        let l = ldl.L();
+        let d = ldl.B_diag().column_vector();
+        let mut sqrt_d = Col::<C64>::zeros(d.nrows());
+        zip!(&mut sqrt_d, d).for_each(|unzip!(sqd, v)| *sqd = v.sqrt());
+        ldl.P().inverse() * l * sqrt_d.as_diagonal()
+    }
+}
+
+/// Eigenvalues of `K^dagger g K` for a Cholesky-like square root `K` of the Hamiltonian,
+/// where `g = diag(I_n, -I_n)` is the bosonic commutation metric (built by negating `K`'s
+/// bottom `n_sites` rows before the product).
+fn eigenvalues_from_sqrt_hamiltonian(sqrt_hamiltonian: Mat<C64>, n_sites: usize) -> Vec<f64> {
     let mut shc: Mat<C64> = sqrt_hamiltonian.clone();
     let mut negative_half = shc.submatrix_mut(n_sites, 0, n_sites, 2 * n_sites);
     negative_half *= -1.;
@@ -176,19 +631,85 @@ fn energies_single_q(
         .expect("Could not calculate eigendecomposition of the Hamiltonian.")
 }
 
+/// Calculate energies (eigenvalues of the Hamiltonian) for a single q-value, along with the
+/// largest regularization shift applied if the regularized LDL^T fallback was used for this
+/// q-point, and the largest `|Im(eigenvalue)|` if the non-Hermitian fallback found a
+/// dynamically unstable mode (see [`calc_energies`] and [`solve_general_bogoliubov`]).
+#[allow(clippy::too_many_arguments)]
+fn energies_single_q(
+    q: Col<f64>,
+    C: &Mat<C64>,
+    n_sites: usize,
+    z: &[Col<C64>],
+    spin_coefficients: &Mat<C64>,
+    couplings: &[&Coupling],
+    Az: &Option<Vec<C64>>,
+    biquadratic_factors: &[C64],
+    anisotropy_ab: &Option<Vec<(C64, C64)>>,
+    epsilon: Option<f64>,
+) -> (Vec<f64>, Option<f64>, Option<f64>) {
+    let solution = calc_sqrt_hamiltonian(
+        q,
+        C,
+        n_sites,
+        z,
+        spin_coefficients,
+        couplings,
+        Az,
+        biquadratic_factors,
+        anisotropy_ab,
+        epsilon,
+    );
+
+    match solution {
+        // 'shc' is "square root of Hamiltonian with commutation"`
+        // We need to enforce the bosonic commutation properties, we do this
+        // by finding the 'square root' of the matrix (i.e. finding K such that KK^dagger = H)
+        // and then negating the second half.
+        //
+        // In matrix form we do
+        //
+        //     M = K^dagger g K
+        //
+        // where g is a diagonal matrix of length 2n, with the first n entries being 1, and the
+        // remaining entries being -1.
+        // We do this by just multiplying the >n_sites rows of shc to get g*K
+        BogoliubovSolution::Cholesky(sqrt_hamiltonian) => {
+            (eigenvalues_from_sqrt_hamiltonian(sqrt_hamiltonian, n_sites), None, None)
+        }
+        BogoliubovSolution::Regularized { sqrt_hamiltonian, shift } => {
+            (eigenvalues_from_sqrt_hamiltonian(sqrt_hamiltonian, n_sites), Some(shift), None)
+        }
+        BogoliubovSolution::General { energies, unstable, .. } => {
+            // ascending order, as in the Colpa branches (`energies` is nonincreasing)
+            let mut all: Vec<f64> = energies.iter().map(|e| -e).collect();
+            all.extend(energies.iter().rev());
+            (all, None, unstable)
+        }
+    }
+}
+
 /// Calculate the block matrices for S'^alpha, beta
 /// That is, the matrix [ Y Z ; V W ] for each alpha, beta pair
+///
+/// `form_factors`, if given, is the per-site analytic `<j0>` neutron form factor
+/// `FormFactor`. Because the form factor is site-dependent, it is folded in here (at
+/// each site's phase factor, giving `f_i(q) * f_j(q)` once the outer product is taken)
+/// rather than as a single overall scalar after the inter-site correlations are summed.
 fn calc_sab_blocks(
     z: &[Col<C64>],
     q: Col<f64>,
     spin_coefficients: &Mat<C64>,
     positions: &[ColRef<f64>],
+    form_factors: &Option<Vec<FormFactor>>,
 ) -> Mat<Mat<C64>> {
-    let phase_factors = Col::<C64>::from_iter(
-        positions
-            .iter()
-            .map(|r_i| (J * (q.transpose() * r_i)).exp()),
-    );
+    let s = q.norm_l2() / (4. * PI);
+    let phase_factors = Col::<C64>::from_iter(positions.iter().enumerate().map(|(i, r_i)| {
+        let f_i = form_factors
+            .as_ref()
+            .map_or(1., |ffs| ffs[i].evaluate(s));
+        (J * (q.transpose() * r_i)).exp() * f_i
+    }));
     let phase_factors_matrix = phase_factors.clone() * phase_factors.adjoint();
 
     let coefficients = component_mul(&spin_coefficients, &phase_factors_matrix);
@@ -238,6 +759,16 @@ fn calc_sab_blocks(
     blocks
 }
 
+/// `epsilon`, if given, enables the regularized LDL^T fallback for q-points where the
+/// Bogoliubov Hamiltonian is not positive definite (see [`calc_energies`]); the fourth
+/// element of the result gives, per q-point, the largest regularization shift applied, or
+/// `None` if no regularization was needed (or `epsilon` was not given); the fifth element
+/// gives, per q-point, the largest `|Im(eigenvalue)|` if the non-Hermitian fallback found a
+/// dynamically unstable mode, or `None` otherwise (see [`solve_general_bogoliubov`]).
+///
+/// In [`SpinMode::GeneralizedSUN`] mode, `field`, `anisotropy`, `form_factors` and `epsilon`
+/// are not yet supported (see [`calc_energies`]) and must be `None`.
+#[allow(clippy::too_many_arguments)]
 pub fn calc_spinwave(
     rotations: Vec<MatRef<C64>>,
     magnitudes: Vec<f64>,
@@ -245,48 +776,184 @@ pub fn calc_spinwave(
     couplings: Vec<&Coupling>,
     positions: Vec<ColRef<f64>>,
     field: Option<MagneticField>,
-) -> (Vec<Vec<f64>>, Vec<Vec<Mat<C64>>>) {
-    let n_sites = rotations.len();
+    anisotropy: Option<Vec<Mat<C64>>>,
+    mode: SpinMode,
+    sun_bases: Option<Vec<&SUNBasis>>,
+    form_factors: Option<Vec<FormFactor>>,
+    epsilon: Option<f64>,
+) -> (
+    Vec<Vec<f64>>,
+    Vec<Vec<Mat<C64>>>,
+    Vec<Vec<Mat<C64>>>,
+    Vec<Option<f64>>,
+    Vec<Option<f64>>,
+) {
+    match mode {
+        SpinMode::Dipole => {
+            let n_sites = rotations.len();
 
-    let (C, z, spin_coefficients, Az) =
-        calc_q_independent(rotations, magnitudes, &couplings, field);
+            let (C, z, spin_coefficients, Az, biquadratic_factors, anisotropy_ab) =
+                calc_q_independent(rotations, magnitudes, &couplings, field, &anisotropy);
+
+            let results: Vec<(Vec<f64>, Vec<Mat<C64>>, Vec<Mat<C64>>, Option<f64>, Option<f64>)> = q_vectors
+                .into_par_iter()
+                .map(|q| {
+                    spinwave_single_q(
+                        Col::from_iter(q),
+                        &C,
+                        n_sites,
+                        &z,
+                        &spin_coefficients,
+                        &couplings,
+                        &positions,
+                        &Az,
+                        &biquadratic_factors,
+                        &anisotropy_ab,
+                        &form_factors,
+                        epsilon,
+                    )
+                })
+                .collect();
+
+            let mut energies = Vec::with_capacity(results.len());
+            let mut sab = Vec::with_capacity(results.len());
+            let mut sublattice_sab = Vec::with_capacity(results.len());
+            let mut regularization_shifts = Vec::with_capacity(results.len());
+            let mut unstable_flags = Vec::with_capacity(results.len());
+            for (e, s, sl, shift, unstable) in results {
+                energies.push(e);
+                sab.push(s);
+                sublattice_sab.push(sl);
+                regularization_shifts.push(shift);
+                unstable_flags.push(unstable);
+            }
+            (energies, sab, sublattice_sab, regularization_shifts, unstable_flags)
+        }
+        SpinMode::GeneralizedSUN => {
+            assert!(
+                field.is_none() && anisotropy.is_none() && form_factors.is_none() && epsilon.is_none(),
+                "field, anisotropy, form_factors and epsilon are not yet supported for \
+                 GeneralizedSUN mode; encode single-ion anisotropy directly in the per-site \
+                 SUNBasis instead"
+            );
+            let bases = sun_bases.expect("SU(N) bases must be provided when mode is GeneralizedSUN");
+            let (energies, sab) = calc_spinwave_sun(bases, q_vectors, couplings, positions);
+            // per-atom sublattice projection, the LDL^T regularization fallback, and the
+            // dynamical-instability flag are not yet implemented for the SU(N) path
+            let sublattice_sab = vec![Vec::new(); energies.len()];
+            let regularization_shifts = vec![None; energies.len()];
+            let unstable_flags = vec![None; energies.len()];
+            (energies, sab, sublattice_sab, regularization_shifts, unstable_flags)
+        }
+    }
+}
+
+/// Expand an `n_sites x n_sites` matrix to `dim x dim` (`dim = n_sites * n_flavors`) by
+/// repeating each entry over the `n_flavors x n_flavors` block of its site pair.
+fn expand_site_matrix(m: &Mat<C64>, n_flavors: usize) -> Mat<C64> {
+    let n_sites = m.nrows();
+    let dim = n_sites * n_flavors;
+    Mat::<C64>::from_fn(dim, dim, |r, c| m[(r / n_flavors, c / n_flavors)])
+}
+
+/// SU(N) analog of [`calc_spinwave`]: for each q-point, solve the generalized Bogoliubov
+/// problem with `n_flavors = N - 1` bosons per site and assemble the correlation tensor
+/// from the paraunitary transform, generalizing [`calc_sab_blocks`] and
+/// [`spinwave_single_q`].
+fn calc_spinwave_sun(
+    bases: Vec<&SUNBasis>,
+    q_vectors: Vec<Vec<f64>>,
+    couplings: Vec<&Coupling>,
+    positions: Vec<ColRef<f64>>,
+) -> (Vec<Vec<f64>>, Vec<Vec<Mat<C64>>>) {
+    let n_sites = bases.len();
+    let n_flavors = sun_flavors(&bases);
+    let dim = n_sites * n_flavors;
 
     q_vectors
         .into_par_iter()
-        .map(|q| {
-            spinwave_single_q(
-                Col::from_iter(q),
-                &C,
-                n_sites,
-                &z,
-                &spin_coefficients,
-                &couplings,
-                &positions,
-                &Az,
-            )
+        .map(|q_raw| {
+            let q = Col::from_iter(q_raw);
+            let sqrt_hamiltonian =
+                calc_sqrt_hamiltonian_sun(q.clone(), &bases, &couplings, n_sites, n_flavors);
+            let mut shc: Mat<C64> = sqrt_hamiltonian.clone();
+            let mut negative_half = shc.submatrix_mut(dim, 0, dim, 2 * dim);
+            negative_half *= -1.;
+
+            let eigendecomp = (sqrt_hamiltonian.adjoint() * shc)
+                .self_adjoint_eigen(Side::Lower)
+                .expect("Could not calculate eigendecomposition of the SU(N) Hamiltonian.");
+
+            let eigvals: ColRef<C64> = eigendecomp.S().column_vector();
+            let eigvecs: MatRef<C64> = eigendecomp.U().reverse_rows();
+
+            let mut sqrt_E = eigvals.reverse_rows().to_owned();
+            let mut negative_half = sqrt_E.subrows_mut(dim, dim);
+            negative_half *= -1.;
+            sqrt_E.iter_mut().for_each(|x| *x = x.sqrt());
+
+            let mut T = eigvecs * sqrt_E.as_diagonal();
+            solve_lower_triangular_in_place(sqrt_hamiltonian.as_ref(), T.as_mut(), Par::Seq);
+            if T.has_nan() {
+                T = Mat::<C64>::zeros(2 * dim, 2 * dim);
+            }
+
+            // phase factors per site (same convention as `calc_sab_blocks`)
+            let phase_factors = Col::<C64>::from_iter(
+                positions.iter().map(|r_i| (J * (q.transpose() * r_i)).exp()),
+            );
+            let phase_factors_matrix = phase_factors.clone() * phase_factors.adjoint();
+            let coefficients = expand_site_matrix(&phase_factors_matrix, n_flavors);
+
+            // v_alpha: concatenate each site's <m|S^alpha|0> row into a length-`dim` vector
+            let v = |alpha: usize| -> Col<C64> {
+                Col::<C64>::from_iter((0..dim).map(|idx| {
+                    let (site, flavor) = (idx / n_flavors, idx % n_flavors);
+                    bases[site].linear_matrix_elements()[(alpha, flavor)]
+                }))
+            };
+            let v_alphas: Vec<Col<C64>> = (0..3).map(v).collect();
+
+            let sab_blocks = Mat::<Mat<C64>>::from_fn(3, 3, |alpha, beta| -> Mat<C64> {
+                let Yab = component_mul(&(v_alphas[alpha].clone() * v_alphas[beta].adjoint()), &coefficients);
+                let Zab = component_mul(&(v_alphas[alpha].clone() * v_alphas[beta].transpose()), &coefficients);
+                let Vab = Zab.conjugate().to_owned();
+                let Wab = Yab.conjugate().to_owned();
+                block_matrix(&Yab, &Zab, &Vab, &Wab)
+            });
+
+            let block_diags = Mat::<Col<C64>>::from_fn(3, 3, |alpha, beta| -> Col<C64> {
+                let mat = T.adjoint() * sab_blocks[(alpha, beta)].as_ref() * T.as_ref();
+                mat.diagonal().column_vector().to_owned()
+            });
+
+            let Sab: Vec<Mat<C64>> = (0..dim)
+                .map(|i| {
+                    Mat::<C64>::from_fn(3, 3, |alpha, beta| -> C64 {
+                        block_diags[(alpha, beta)].as_ref()[i] / (2 * dim) as f64
+                    })
+                })
+                .chain((0..dim).map(|i| {
+                    Mat::<C64>::from_fn(3, 3, |alpha, beta| -> C64 {
+                        block_diags[(alpha, beta)].as_ref()[dim - i] / (2 * dim) as f64
+                    })
+                }))
+                .collect();
+
+            (eigvals.iter().map(|x| x.re).collect(), Sab)
         })
         .collect()
 }
 
-/// Calculate energies and intensities for a single q-point.
-fn spinwave_single_q(
-    q: Col<f64>,
-    C: &Mat<C64>,
-    n_sites: usize,
-    z: &[Col<C64>],
-    spin_coefficients: &Mat<C64>,
-    couplings: &[&Coupling],
-    positions: &[ColRef<f64>],
-    Az: &Option<Vec<C64>>,
-) -> (Vec<f64>, Vec<Mat<C64>>) {
-    let sqrt_hamiltonian =
-        calc_sqrt_hamiltonian(q.clone(), C, n_sites, z, spin_coefficients, couplings, Az);
+/// Diagonalize a Cholesky-like square root `K` of the Hamiltonian (genuine or regularized)
+/// into the magnon energies and the paraunitary transform `T`, following Colpa's method.
+/// The energies are returned in ascending order; `T`'s columns are in the opposite,
+/// nonincreasing order.
+fn modes_from_sqrt_hamiltonian(sqrt_hamiltonian: Mat<C64>, n_sites: usize) -> (Vec<f64>, Mat<C64>) {
     let mut shc: Mat<C64> = sqrt_hamiltonian.clone();
     let mut negative_half = shc.submatrix_mut(n_sites, 0, n_sites, 2 * n_sites);
     negative_half *= -1.;
 
-    let sab_blocks = calc_sab_blocks(z, q, spin_coefficients, positions);
-
     let eigendecomp = (sqrt_hamiltonian.adjoint() * shc)
         .self_adjoint_eigen(Side::Lower)
         .expect("Could not calculate eigendecomposition of the Hamiltonian.");
@@ -314,11 +981,82 @@ fn spinwave_single_q(
     let mut T = eigvecs * sqrt_E.as_diagonal();
     solve_lower_triangular_in_place(sqrt_hamiltonian.as_ref(), T.as_mut(), Par::Seq);
 
+    (eigvals.iter().map(|x| x.re).collect(), T)
+}
+
+/// Extract the Bogoliubov mode energies (in ascending order) and paraunitary transform `T`
+/// (columns in nonincreasing energy order, see [`modes_from_sqrt_hamiltonian`]) from a
+/// [`BogoliubovSolution`], replacing `T` with zeroes if it is NaN (which happens when there
+/// are zero eigenvalues, e.g. at a Goldstone mode), plus the largest regularization shift applied if the
+/// regularized LDL^T fallback was used for this q-point, and the largest `|Im(eigenvalue)|`
+/// if the non-Hermitian fallback found a dynamically unstable mode (see [`calc_energies`]
+/// and [`solve_general_bogoliubov`]).
+pub(crate) fn bogoliubov_modes(
+    solution: BogoliubovSolution,
+    n_sites: usize,
+) -> (Vec<f64>, Mat<C64>, Option<f64>, Option<f64>) {
+    let (eigvals, mut T, regularization_shift, unstable): (Vec<f64>, Mat<C64>, Option<f64>, Option<f64>) =
+        match solution {
+            BogoliubovSolution::Cholesky(sqrt_hamiltonian) => {
+                let (eigvals, T) = modes_from_sqrt_hamiltonian(sqrt_hamiltonian, n_sites);
+                (eigvals, T, None, None)
+            }
+            BogoliubovSolution::Regularized { sqrt_hamiltonian, shift } => {
+                let (eigvals, T) = modes_from_sqrt_hamiltonian(sqrt_hamiltonian, n_sites);
+                (eigvals, T, Some(shift), None)
+            }
+            BogoliubovSolution::General { energies, T, unstable } => {
+                // ascending order, as in the Colpa branches (`energies` is nonincreasing)
+                let mut eigvals: Vec<f64> = energies.iter().map(|e| -e).collect();
+                eigvals.extend(energies.iter().rev());
+                (eigvals, T, None, unstable)
+            }
+        };
+
     // T is NaN if there are zero eigenvalues; set to zeroes
     if T.has_nan() {
         T = Mat::<C64>::zeros(2 * n_sites, 2 * n_sites);
     }
 
+    (eigvals, T, regularization_shift, unstable)
+}
+
+/// Calculate energies and intensities for a single q-point, along with the largest
+/// regularization shift applied if the regularized LDL^T fallback was used for this
+/// q-point, and the largest `|Im(eigenvalue)|` if the non-Hermitian fallback found a
+/// dynamically unstable mode (see [`calc_energies`] and [`solve_general_bogoliubov`]).
+#[allow(clippy::too_many_arguments)]
+fn spinwave_single_q(
+    q: Col<f64>,
+    C: &Mat<C64>,
+    n_sites: usize,
+    z: &[Col<C64>],
+    spin_coefficients: &Mat<C64>,
+    couplings: &[&Coupling],
+    positions: &[ColRef<f64>],
+    Az: &Option<Vec<C64>>,
+    biquadratic_factors: &[C64],
+    anisotropy_ab: &Option<Vec<(C64, C64)>>,
+    form_factors: &Option<Vec<FormFactor>>,
+    epsilon: Option<f64>,
+) -> (Vec<f64>, Vec<Mat<C64>>, Vec<Mat<C64>>, Option<f64>, Option<f64>) {
+    let solution = calc_sqrt_hamiltonian(
+        q.clone(),
+        C,
+        n_sites,
+        z,
+        spin_coefficients,
+        couplings,
+        Az,
+        biquadratic_factors,
+        anisotropy_ab,
+        epsilon,
+    );
+
+    let sab_blocks = calc_sab_blocks(z, q, spin_coefficients, positions, form_factors);
+
+    let (eigvals, mut T, regularization_shift, unstable) = bogoliubov_modes(solution, n_sites);
+
     // Apply transformation matrix to S'^alpha,beta block matrices T*[VW;YZ]T
     // and then we just take the diagonal elements as that's all we need for
     // S'^alpha,beta(k, omega) at each eigenvalue
@@ -345,5 +1083,44 @@ fn spinwave_single_q(
         }))
         .collect();
 
-    (eigvals.iter().map(|x| x.re).collect(), Sab)
+    // per-atom sublattice/polarization projection of Sab: weight each raw mode's
+    // contribution to Sab by how much of the Bogoliubov eigenvector `T` for that mode
+    // lives on atom `i` (both its particle and hole components), normalized across atoms
+    // so that summing the projection over all atoms recovers `Sab`.
+    let dim = 2 * n_sites;
+    let mut site_weight = Mat::<f64>::zeros(n_sites, dim);
+    for site in 0..n_sites {
+        for mode in 0..dim {
+            site_weight[(site, mode)] =
+                T[(site, mode)].norm_sqr() + T[(n_sites + site, mode)].norm_sqr();
+        }
+    }
+    let total_weight: Vec<f64> = (0..dim)
+        .map(|mode| {
+            let total: f64 = (0..n_sites).map(|site| site_weight[(site, mode)]).sum();
+            if total == 0. {
+                1.
+            } else {
+                total
+            }
+        })
+        .collect();
+
+    // raw mode index feeding each entry of `Sab`, in the same order used to build it above
+    let raw_indices: Vec<usize> = (0..n_sites).chain((0..n_sites).map(|i| n_sites - i)).collect();
+
+    let sublattice_sab: Vec<Mat<C64>> = (0..n_sites)
+        .map(|site| {
+            // row = mode (matching `Sab`'s ordering), column = flattened alpha * 3 + beta
+            Mat::<C64>::from_fn(dim, 9, |mode_idx, ab| -> C64 {
+                let raw = raw_indices[mode_idx];
+                let (alpha, beta) = (ab / 3, ab % 3);
+                let diag: ColRef<C64> = block_diags[(alpha, beta)].as_ref();
+                let weight = site_weight[(site, raw)] / total_weight[raw];
+                diag[raw] / (dim as f64) * weight
+            })
+        })
+        .collect();
+
+    (eigvals, Sab, sublattice_sab, regularization_shift, unstable)
 }
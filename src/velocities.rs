@@ -0,0 +1,143 @@
+//! Analytic magnon group velocities `v_n(q) = dE_n/dq` in the Bogoliubov eigenbasis.
+use faer::{Col, Mat, MatRef, Side};
+use rayon::prelude::*;
+
+use crate::berry::{dynamical_matrix_gradient, sigma_diag};
+use crate::spinwave::{bogoliubov_modes, calc_q_independent, calc_sqrt_hamiltonian};
+use crate::{Coupling, MagneticField, C64};
+
+/// Energy gap below which two bands at the same q-point are treated as degenerate and
+/// diagonalized together rather than read off the diagonal individually.
+const DEGENERACY_TOL: f64 = 1e-8;
+
+/// Group consecutive band indices (assumed already sorted in the usual nonincreasing
+/// Colpa order) whose energies agree to within [`DEGENERACY_TOL`].
+fn degenerate_groups(eigvals: &[f64]) -> Vec<Vec<usize>> {
+    let mut groups: Vec<Vec<usize>> = Vec::new();
+    for (n, &e) in eigvals.iter().enumerate() {
+        match groups.last_mut() {
+            Some(group) if (e - eigvals[group[0]]).abs() < DEGENERACY_TOL => group.push(n),
+            _ => groups.push(vec![n]),
+        }
+    }
+    groups
+}
+
+/// Calculate the group velocity `v_n(q) = <n|Sigma dH/dq|n>` of every Bogoliubov band at
+/// a single q-point, for all three Cartesian components of q. Degenerate bands are
+/// handled by degenerate perturbation theory: within a group of bands sharing the same
+/// energy, the velocity operator's restriction to that subspace is diagonalized, rather
+/// than read off the (ill-defined) diagonal directly.
+#[allow(clippy::too_many_arguments)]
+fn band_velocities_single_q(
+    q: Col<f64>,
+    C: &Mat<C64>,
+    n_sites: usize,
+    z: &[Col<C64>],
+    spin_coefficients: &Mat<C64>,
+    couplings: &[&Coupling],
+    Az: &Option<Vec<C64>>,
+    biquadratic_factors: &[C64],
+    anisotropy_ab: &Option<Vec<(C64, C64)>>,
+) -> Mat<f64> {
+    let dim = 2 * n_sites;
+
+    let solution = calc_sqrt_hamiltonian(
+        q.clone(),
+        C,
+        n_sites,
+        z,
+        spin_coefficients,
+        couplings,
+        Az,
+        biquadratic_factors,
+        anisotropy_ab,
+        None,
+    );
+    let (eigvals, T, _, _) = bogoliubov_modes(solution, n_sites);
+
+    // `bogoliubov_modes` returns `eigvals` in ascending order but `T`'s columns (and hence
+    // `M`'s basis below) in the opposite, nonincreasing order (see
+    // `modes_from_sqrt_hamiltonian`); reverse it here so degenerate-subspace grouping
+    // operates on the same band ordering as `T`.
+    let eigvals_descending: Vec<f64> = eigvals.iter().rev().copied().collect();
+    let groups = degenerate_groups(&eigvals_descending);
+    let sigma = sigma_diag(n_sites);
+
+    let mut velocities = Mat::<f64>::zeros(dim, 3);
+    for direction in 0..3 {
+        let dH = dynamical_matrix_gradient(
+            &q,
+            direction,
+            n_sites,
+            z,
+            spin_coefficients,
+            couplings,
+            biquadratic_factors,
+        );
+        let M = T.adjoint() * sigma.as_diagonal() * dH * T.as_ref();
+
+        for group in &groups {
+            if group.len() == 1 {
+                let n = group[0];
+                velocities[(n, direction)] = M[(n, n)].re;
+                continue;
+            }
+
+            let m = group.len();
+            let sub = Mat::<C64>::from_fn(m, m, |r, c| M[(group[r], group[c])]);
+            let eigendecomp = sub
+                .self_adjoint_eigen(Side::Lower)
+                .expect("Could not diagonalize the velocity operator in the degenerate subspace.");
+            let sub_eigvals = eigendecomp.S().column_vector();
+            for (k, &n) in group.iter().enumerate() {
+                velocities[(n, direction)] = sub_eigvals[k].re;
+            }
+        }
+    }
+
+    // `velocities`' rows are in the same nonincreasing order as `T`'s columns; reverse back
+    // to the ascending order `energies()` returns so the two line up band-for-band.
+    Mat::<f64>::from_fn(dim, 3, |r, c| velocities[(dim - 1 - r, c)])
+}
+
+/// Calculate the analytic magnon group velocity `v_n(q) = dE_n/dq` of every Bogoliubov
+/// band at every q-vector, built from the same `dH/dq` used for
+/// [`crate::berry::berry_curvature`] via the Hellmann-Feynman-like relation
+/// `v_n(q) = <n|Sigma dH/dq|n>`.
+///
+/// Only the [`crate::SpinMode::Dipole`] mode is supported; the generalized SU(N) group
+/// velocity is not yet implemented.
+///
+/// # Returns
+/// A vector over q, where each element is a `2 * n_sites x 3` matrix giving the velocity
+/// vector of every Bogoliubov band, in the same ascending-energy order `energies()` returns.
+pub fn band_velocities(
+    rotations: Vec<MatRef<C64>>,
+    magnitudes: Vec<f64>,
+    q_vectors: Vec<Vec<f64>>,
+    couplings: Vec<&Coupling>,
+    field: Option<MagneticField>,
+    anisotropy: Option<Vec<Mat<C64>>>,
+) -> Vec<Mat<f64>> {
+    let n_sites = rotations.len();
+    let (C, z, spin_coefficients, Az, biquadratic_factors, anisotropy_ab) =
+        calc_q_independent(rotations, magnitudes, &couplings, field, &anisotropy);
+
+    q_vectors
+        .into_par_iter()
+        .map(|q| {
+            band_velocities_single_q(
+                Col::from_iter(q),
+                &C,
+                n_sites,
+                &z,
+                &spin_coefficients,
+                &couplings,
+                &Az,
+                &biquadratic_factors,
+                &anisotropy_ab,
+            )
+        })
+        .collect()
+}